@@ -6,12 +6,12 @@ pub fn part_one(input: &str) -> Option<u32> {
 
     //Part 1
     for line in lines.clone() {
-        let fst_char = line.chars().into_iter().find(|c| c.is_digit(10));
+        let fst_char = line.chars().into_iter().find(|c| c.is_ascii_digit());
         if fst_char.is_none() {
             continue;
         }
 
-        let last_char = line.chars().rev().into_iter().find(|c| c.is_digit(10));
+        let last_char = line.chars().rev().into_iter().find(|c| c.is_ascii_digit());
 
         let mut digit1 = 0;
         if let Some(char1) = fst_char {
@@ -32,8 +32,8 @@ pub fn part_one(input: &str) -> Option<u32> {
 
 fn get_first(line: &str, numbers: Vec<&str>) -> Option<u32> {
     let fst_digit = (
-        line.chars().into_iter().find(|c| c.is_digit(10)),
-        line.chars().into_iter().position(|c| c.is_digit(10)),
+        line.chars().into_iter().find(|c| c.is_ascii_digit()),
+        line.chars().into_iter().position(|c| c.is_ascii_digit()),
     );
 
     let indices: Vec<(&str, usize)> = numbers
@@ -47,27 +47,25 @@ fn get_first(line: &str, numbers: Vec<&str>) -> Option<u32> {
         return None;
     }
 
-    let mut fst: u32 = 0;
-
-    if let Some(fst_ind) = fst_digit.1 {
+    let fst = if let Some(fst_ind) = fst_digit.1 {
         if let Some((numb, fst_num_ind)) = fst_number {
             if fst_ind < fst_num_ind {
-                fst = fst_digit.0.unwrap().to_digit(10).unwrap();
+                fst_digit.0.unwrap().to_digit(10).unwrap()
             } else {
-                fst = word_to_number(numb);
+                word_to_number(numb)
             }
         } else {
-            fst = fst_digit.0.unwrap().to_digit(10).unwrap();
+            fst_digit.0.unwrap().to_digit(10).unwrap()
         }
     } else {
-        fst = word_to_number(fst_number.unwrap().0);
-    }
+        word_to_number(fst_number.unwrap().0)
+    };
 
     if fst == 0 {
         return None;
     }
 
-    return Some(fst);
+    Some(fst)
 }
 
 fn word_to_number(word: &str) -> u32 {
@@ -89,7 +87,6 @@ pub fn part_two(input: &str) -> Option<u32> {
     let lines: Vec<&str> = input.split("\n").collect();
 
     let mut res2 = 0;
-    let mut ind = 1;
     for line in lines {
         let numbers: Vec<&str> = vec![
             "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
@@ -120,30 +117,8 @@ pub fn part_two(input: &str) -> Option<u32> {
 
         let value = digit1 * 10 + digit2;
 
-        ind += 1;
         res2 += value;
     }
 
     Some(res2)
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 1,
-        ));
-        assert_eq!(result, Some(142));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
-        ));
-        assert_eq!(result, Some(281));
-    }
-}
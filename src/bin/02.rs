@@ -1,4 +1,4 @@
-advent_of_code::solution!(2);
+advent_of_code::solution!(2, fuzz = games);
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Color {
@@ -9,7 +9,7 @@ pub enum Color {
 
 //Parse string to Color
 impl Color {
-    pub fn from_str(s: &str) -> Option<Color> {
+    pub fn parse(s: &str) -> Option<Color> {
         let split_comma = s.split(" ").collect::<Vec<&str>>();
         let color = split_comma[1].trim();
         let value = split_comma[0].trim().parse().unwrap();
@@ -32,9 +32,9 @@ impl Color {
 
     pub fn value(&self) -> u32 {
         match self {
-            Color::Red(value) => value.clone(),
-            Color::Green(value) => value.clone(),
-            Color::Blue(value) => value.clone(),
+            Color::Red(value) => *value,
+            Color::Green(value) => *value,
+            Color::Blue(value) => *value,
         }
     }
 }
@@ -53,7 +53,7 @@ pub fn part_one(input: &str) -> Option<u32> {
             let colors = game.split(",").collect::<Vec<&str>>();
 
             for col in colors {
-                let color = Color::from_str(col.trim());
+                let color = Color::parse(col.trim());
 
                 if color.is_none() {
                     continue;
@@ -128,7 +128,7 @@ pub fn part_two(input: &str) -> Option<u32> {
             let colors = game.split(",").collect::<Vec<&str>>();
 
             for col in colors {
-                let color = Color::from_str(col.trim());
+                let color = Color::parse(col.trim());
 
                 if color.is_none() {
                     continue;
@@ -145,19 +145,25 @@ pub fn part_two(input: &str) -> Option<u32> {
     
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(8));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(2286));
-    }
+#[cfg(all(test, feature = "fuzz"))]
+fn games(rng: &mut advent_of_code::template::fuzz::Rng) -> String {
+    (1..=rng.range(1, 6))
+        .map(|game| {
+            let sets: Vec<String> = (0..rng.range(1, 4))
+                .map(|_| {
+                    let cubes: Vec<String> = ["red", "green", "blue"]
+                        .iter()
+                        .filter_map(|color| {
+                            let count = rng.range(0, 20);
+                            // colors without cubes are left out.
+                            (count > 0).then(|| format!("{count} {color}"))
+                        })
+                        .collect();
+                    cubes.join(", ")
+                })
+                .collect();
+            format!("Game {game}: {}", sets.join("; "))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
 }
@@ -1,4 +1,4 @@
-advent_of_code::solution!(3);
+advent_of_code::solution!(3, fuzz = schematic);
 
 #[derive(Debug)]
 struct Number {
@@ -29,27 +29,23 @@ impl Symbol {
             let num_digits = (number.value as f32).log10() as u32 + 1;
 
             for i in 0..num_digits {
-                let xi = nx - i;
-                if (xi == x && (ny == y + 1 || ny == y - 1))
-                    || (ny == y && (xi == x + 1 || xi == x - 1))
-                    || (xi == x + 1 && ny == y + 1)
-                    || (xi == x - 1 && ny == y - 1)
-                    || (xi == x + 1 && ny == y - 1)
-                    || (xi == x - 1 && ny == y + 1)
-                {
+                if are_neighbours((nx - i, ny), (x, y)) {
                     adjacent_numbers.push(number);
                     break;
                 }
             }
         }
 
-        //multiply all adjacent numbers
+        //multiply the adjacent numbers of gears, symbols next to more numbers could overflow
+        let is_gear = adjacent_numbers.len() == 2;
         let mut result = 1;
-        for number in adjacent_numbers.iter() {
-            result *= number.value;
+        if is_gear {
+            for number in adjacent_numbers.iter() {
+                result *= number.value;
+            }
         }
 
-        (adjacent_numbers.len() == 2, Number::new(result, (x, y)))
+        (is_gear, Number::new(result, (x, y)))
     }
 }
 
@@ -64,14 +60,7 @@ impl Number {
         let num_digits = (self.value as f32).log10() as u32 + 1;
 
         for i in 0..num_digits {
-            let xi = x - i;
-            if (xi == sx && (y == sy + 1 || y == sy - 1))
-                || (y == sy && (xi == sx + 1 || xi == sx - 1))
-                || (xi == sx + 1 && y == sy + 1)
-                || (xi == sx - 1 && y == sy - 1)
-                || (xi == sx + 1 && y == sy - 1)
-                || (xi == sx - 1 && y == sy + 1)
-            {
+            if are_neighbours((x - i, y), (sx, sy)) {
                 return true;
             }
         }
@@ -80,6 +69,12 @@ impl Number {
     }
 }
 
+/// Whether two cells touch, diagonally included. Compares distances, as cells on the first row or column have no
+/// neighbour before them.
+fn are_neighbours(a: (u32, u32), b: (u32, u32)) -> bool {
+    a != b && a.0.abs_diff(b.0) <= 1 && a.1.abs_diff(b.1) <= 1
+}
+
 fn is_a_symbol(c: char) -> bool {
     !c.is_numeric() && c != '.'
 }
@@ -111,9 +106,13 @@ pub fn part_one(input: &str) -> Option<u32> {
         })
         .collect();
 
+    let Some(first) = numbers.first() else {
+        return Some(0);
+    };
+
     let mut combined_numbers = Vec::new();
-    let mut current_number = numbers[0].value;
-    let mut current_position = numbers[0].position;
+    let mut current_number = first.value;
+    let mut current_position = first.position;
 
     for number in numbers.iter().skip(1) {
         if number.position.0 == current_position.0 + 1 && number.position.1 == current_position.1 {
@@ -169,9 +168,13 @@ pub fn part_two(input: &str) -> Option<u32> {
         })
         .collect();
 
+    let Some(first) = numbers.first() else {
+        return Some(0);
+    };
+
     let mut combined_numbers = Vec::new();
-    let mut current_number = numbers[0].value;
-    let mut current_position = numbers[0].position;
+    let mut current_number = first.value;
+    let mut current_position = first.position;
 
     for number in numbers.iter().skip(1) {
         if number.position.0 == current_position.0 + 1 && number.position.1 == current_position.1 {
@@ -203,19 +206,27 @@ pub fn part_two(input: &str) -> Option<u32> {
     Some(result)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(4361));
+#[cfg(all(test, feature = "fuzz"))]
+fn schematic(rng: &mut advent_of_code::template::fuzz::Rng) -> String {
+    let width = rng.range(1, 12) as usize;
+    let mut lines = vec![];
+
+    for _ in 0..rng.range(1, 12) {
+        let mut line = rng.string(".....*#+$", width);
+        // part numbers have up to three digits and are surrounded by other cells.
+        for _ in 0..rng.range(0, 3) {
+            let number = rng.range(1, 999).to_string();
+            let start = rng.range(0, width as u64 - 1) as usize;
+            let end = (start + number.len()).min(width);
+            if line[start.saturating_sub(1)..(end + 1).min(width)]
+                .bytes()
+                .all(|c| !c.is_ascii_digit())
+            {
+                line.replace_range(start..end, &number[..end - start]);
+            }
+        }
+        lines.push(line);
     }
 
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(467835));
-    }
+    lines.join("\n")
 }
@@ -1,11 +1,10 @@
 use std::collections::HashSet;
 
-advent_of_code::solution!(4);
+advent_of_code::solution!(4, fuzz = cards);
 
 pub fn part_one(input: &str) -> Option<u32> {
     let res: Vec<u32> = input
         .lines()
-        .into_iter()
         .map(|line| {
             let numbers = line.split(":").collect::<Vec<&str>>()[1];
             let splited: Vec<&str> = numbers.split("|").collect();
@@ -66,7 +65,7 @@ pub fn part_two(input: &str) -> Option<u32> {
 fn get_matches<'a>(line: &'a str, winning: &mut Vec<&'a str>) -> usize {
     let mut words = line.split_whitespace().skip(2);
     winning.clear();
-    while let Some(x) = words.next() {
+    for x in words.by_ref() {
         if x == "|" {
             break;
         }
@@ -77,19 +76,20 @@ fn get_matches<'a>(line: &'a str, winning: &mut Vec<&'a str>) -> usize {
     words.filter(|x| winning.contains(x)).count()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(13));
+#[cfg(all(test, feature = "fuzz"))]
+fn cards(rng: &mut advent_of_code::template::fuzz::Rng) -> String {
+    fn numbers(rng: &mut advent_of_code::template::fuzz::Rng, count: usize) -> String {
+        (0..count)
+            .map(|_| format!("{:>2}", rng.range(1, 99)))
+            .collect::<Vec<_>>()
+            .join(" ")
     }
 
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(30));
-    }
+    (1..=rng.range(1, 10))
+        .map(|card| {
+            let winning = numbers(rng, 5);
+            format!("Card {card:>3}: {winning} | {}", numbers(rng, 8))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
 }
@@ -0,0 +1,261 @@
+use std::ops::Range;
+
+use advent_of_code::template::params::Param;
+
+advent_of_code::solution!(5, params = [INVERSE]);
+
+const INVERSE: Param<bool> = Param::new(
+    "inverse",
+    false,
+    "Print the seeds of the lowest location of part two, walking the maps backwards.",
+);
+
+/// One line of a map block: `destination source length`.
+#[derive(Debug, Clone)]
+struct MapRange {
+    destination: u64,
+    source: u64,
+    length: u64,
+}
+
+impl MapRange {
+    fn source_range(&self) -> Range<u64> {
+        self.source..self.source + self.length
+    }
+
+    fn destination_range(&self) -> Range<u64> {
+        self.destination..self.destination + self.length
+    }
+
+    fn map(&self, value: u64) -> Option<u64> {
+        if self.source_range().contains(&value) {
+            Some(value - self.source + self.destination)
+        } else {
+            None
+        }
+    }
+
+    fn unmap(&self, value: u64) -> Option<u64> {
+        if self.destination_range().contains(&value) {
+            Some(value - self.destination + self.source)
+        } else {
+            None
+        }
+    }
+}
+
+/// A `x-to-y map:` block. Values outside of every range map to themselves.
+#[derive(Debug, Clone)]
+struct Map {
+    name: String,
+    ranges: Vec<MapRange>,
+}
+
+impl Map {
+    fn map(&self, value: u64) -> u64 {
+        self.ranges
+            .iter()
+            .find_map(|range| range.map(value))
+            .unwrap_or(value)
+    }
+
+    /// All values that map to `value`. There can be several: one per range covering it, plus `value` itself
+    /// when no range covers it as a source.
+    fn unmap(&self, value: u64) -> Vec<u64> {
+        let mut sources: Vec<u64> = self
+            .ranges
+            .iter()
+            .filter_map(|range| range.unmap(value))
+            .collect();
+
+        if !self
+            .ranges
+            .iter()
+            .any(|range| range.source_range().contains(&value))
+        {
+            sources.push(value);
+        }
+
+        sources
+    }
+
+    /// Maps whole ranges by splitting them on the boundaries of the map ranges, without expanding them.
+    fn map_ranges(&self, inputs: &[Range<u64>]) -> Vec<Range<u64>> {
+        let mut mapped = vec![];
+        let mut pending = inputs.to_vec();
+
+        for map_range in &self.ranges {
+            let source = map_range.source_range();
+            let mut unmatched = vec![];
+
+            for input in pending {
+                let before = input.start..input.end.min(source.start);
+                let overlap = input.start.max(source.start)..input.end.min(source.end);
+                let after = input.start.max(source.end)..input.end;
+
+                if !before.is_empty() {
+                    unmatched.push(before);
+                }
+                if !overlap.is_empty() {
+                    let offset = map_range.destination;
+                    mapped.push(
+                        overlap.start - source.start + offset..overlap.end - source.start + offset,
+                    );
+                }
+                if !after.is_empty() {
+                    unmatched.push(after);
+                }
+            }
+
+            pending = unmatched;
+        }
+
+        mapped.extend(pending);
+        mapped
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Almanac {
+    seeds: Vec<u64>,
+    maps: Vec<Map>,
+}
+
+impl Almanac {
+    fn location(&self, seed: u64) -> u64 {
+        self.maps.iter().fold(seed, |value, map| map.map(value))
+    }
+
+    fn location_ranges(&self, seeds: Vec<Range<u64>>) -> Vec<Range<u64>> {
+        self.maps
+            .iter()
+            .fold(seeds, |ranges, map| map.map_ranges(&ranges))
+    }
+
+    /// Walks the maps backwards and returns every seed that ends up at `location`. Useful to debug answers.
+    fn seeds_for_location(&self, location: u64) -> Vec<u64> {
+        let mut seeds: Vec<u64> = self.maps.iter().rev().fold(vec![location], |values, map| {
+            values.iter().flat_map(|&value| map.unmap(value)).collect()
+        });
+
+        seeds.sort_unstable();
+        seeds.dedup();
+        seeds
+    }
+
+    fn seed_ranges(&self) -> Vec<Range<u64>> {
+        self.seeds
+            .chunks(2)
+            .map(|pair| pair[0]..pair[0] + pair[1])
+            .collect()
+    }
+}
+
+fn parse_input(input: &str) -> Almanac {
+    let mut blocks = input.split("\n\n");
+
+    let seeds = blocks
+        .next()
+        .unwrap()
+        .trim_start_matches("seeds:")
+        .split_whitespace()
+        .map(|seed| seed.parse().unwrap())
+        .collect();
+
+    let maps = blocks
+        .map(|block| {
+            let mut lines = block.lines();
+            let name = lines.next().unwrap().trim_end_matches(" map:").to_string();
+
+            let ranges = lines
+                .map(|line| {
+                    let numbers: Vec<u64> = line
+                        .split_whitespace()
+                        .map(|number| number.parse().unwrap())
+                        .collect();
+
+                    MapRange {
+                        destination: numbers[0],
+                        source: numbers[1],
+                        length: numbers[2],
+                    }
+                })
+                .collect();
+
+            Map { name, ranges }
+        })
+        .collect();
+
+    Almanac { seeds, maps }
+}
+
+pub fn part_one(input: &str) -> Option<u64> {
+    let almanac = parse_input(input);
+
+    almanac
+        .seeds
+        .iter()
+        .map(|&seed| almanac.location(seed))
+        .min()
+}
+
+pub fn part_two(input: &str) -> Option<u64> {
+    let almanac = parse_input(input);
+
+    let lowest = almanac
+        .location_ranges(almanac.seed_ranges())
+        .iter()
+        .map(|range| range.start)
+        .min()?;
+
+    // on stderr, so that the results on stdout stay parsable.
+    if INVERSE.get() {
+        let maps: Vec<&str> = almanac.maps.iter().map(|map| map.name.as_str()).collect();
+        eprintln!(
+            "location {lowest} comes from seeds {:?} through maps {}",
+            almanac.seeds_for_location(lowest),
+            maps.join(", ")
+        );
+    }
+
+    Some(lowest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inverse_mapping() {
+        let almanac = parse_input(&advent_of_code::template::read_file("examples", DAY));
+
+        for seed in [79, 14, 55, 13, 82] {
+            let location = almanac.location(seed);
+            assert!(almanac.seeds_for_location(location).contains(&seed));
+        }
+
+        // the lowest location reachable from the seed ranges, found by walking backwards.
+        let seed_ranges = almanac.seed_ranges();
+        let lowest = (0..)
+            .find(|&location| {
+                almanac
+                    .seeds_for_location(location)
+                    .iter()
+                    .any(|seed| seed_ranges.iter().any(|range| range.contains(seed)))
+            })
+            .unwrap();
+        assert_eq!(lowest, 46);
+    }
+
+    #[test]
+    fn test_map_ranges_preserves_size() {
+        let almanac = parse_input(&advent_of_code::template::read_file("examples", DAY));
+        let seeds = almanac.seed_ranges();
+
+        let total = |ranges: &[Range<u64>]| ranges.iter().map(|r| r.end - r.start).sum::<u64>();
+        assert_eq!(
+            total(&almanac.location_ranges(seeds.clone())),
+            total(&seeds)
+        );
+    }
+}
@@ -26,7 +26,7 @@ impl Race {
     pub fn get_all_wr(&self) -> usize {
         let wr = self.get_all_wr_distances();
 
-        wr.iter().count()
+        wr.len()
     }
 
     fn get_distance_at_time(&self, time: u64) -> u64 {
@@ -43,7 +43,6 @@ impl Race {
 fn get_times(input: &str) -> Vec<u64> {
     input
         .split("\n")
-        .into_iter()
         .take(1)
         .flat_map(|str| str.split_whitespace())
         .skip(1)
@@ -54,7 +53,6 @@ fn get_times(input: &str) -> Vec<u64> {
 fn get_solo_times(input: &str) -> u64 {
     let data = input
         .split("\n")
-        .into_iter()
         .take(1)
         .flat_map(|str| str.split_whitespace())
         .skip(1)
@@ -63,7 +61,7 @@ fn get_solo_times(input: &str) -> u64 {
     let mut result = String::new(); // String to store the result
 
     for num in data {
-        result.push_str(&num.to_string());
+        result.push_str(num);
     }
 
     result.parse().unwrap()
@@ -72,7 +70,6 @@ fn get_solo_times(input: &str) -> u64 {
 fn get_solo_distance(input: &str) -> u64 {
     let data = input
         .split("\n")
-        .into_iter()
         .skip(1)
         .flat_map(|str| str.split_whitespace())
         .skip(1)
@@ -81,7 +78,7 @@ fn get_solo_distance(input: &str) -> u64 {
     let mut result = String::new();
 
     for num in data {
-        result.push_str(&num.to_string());
+        result.push_str(num);
     }
 
     result.parse().unwrap()
@@ -90,7 +87,6 @@ fn get_solo_distance(input: &str) -> u64 {
 fn get_distance(input: &str) -> Vec<u64> {
     input
         .split("\n")
-        .into_iter()
         .skip(1)
         .flat_map(|str| str.split_whitespace())
         .skip(1)
@@ -109,7 +105,7 @@ pub fn part_one(input: &str) -> Option<u64> {
 
     let records: Vec<usize> = races.iter().map(|race| race.get_all_wr()).collect();
 
-    let res = records.iter().fold(1, |acc, x| acc * x);
+    let res = records.iter().product::<usize>();
 
     Some(res as u64)
 }
@@ -122,20 +118,3 @@ pub fn part_two(input: &str) -> Option<u64> {
 
     Some(record as u64)
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result: Option<u64> = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(288));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(71503));
-    }
-}
@@ -1,13 +1,10 @@
-use std::{
-    collections::{HashMap, HashSet},
-    sync::{Arc, Mutex},
-};
+use std::collections::HashSet;
 
-use indicatif::{ParallelProgressIterator, ProgressIterator};
-use once_cell::sync::Lazy;
+use advent_of_code::template::{memo::Memo, progress};
+use indicatif::ParallelProgressIterator;
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 
-advent_of_code::solution!(8);
+advent_of_code::solution!(8, parse = parse_input, fuzz = networks);
 
 #[derive(Debug, Clone)]
 enum Instruction {
@@ -22,7 +19,15 @@ struct Node {
     right: String,
 }
 
-fn parse_input(input: &str) -> (Vec<Instruction>, Vec<Node>) {
+#[derive(Debug, Clone)]
+pub struct Network {
+    instructions: Vec<Instruction>,
+    nodes: Vec<Node>,
+}
+
+pub fn parse_input(input: &str) -> Network {
+    let re = regex::Regex::new(r"(\w+) = \((\w+), (\w+)\)").unwrap();
+
     let instructions: Vec<Instruction> = input
         .lines()
         .take(1)
@@ -39,7 +44,6 @@ fn parse_input(input: &str) -> (Vec<Instruction>, Vec<Node>) {
         .lines()
         .skip(2)
         .map(|line| {
-            let re = regex::Regex::new(r"(\w+) = \((\w+), (\w+)\)").unwrap();
             let caps = re.captures(line).unwrap();
 
             let current = caps.get(1).map_or("", |m| m.as_str()).to_string();
@@ -54,29 +58,28 @@ fn parse_input(input: &str) -> (Vec<Instruction>, Vec<Node>) {
         })
         .collect();
 
-    (instructions, nodes)
+    Network {
+        instructions,
+        nodes,
+    }
 }
 
-static SAVED_NODES: Lazy<Arc<Mutex<HashMap<String, Node>>>> =
-    Lazy::new(|| Arc::new(Mutex::new(HashMap::new())));
-
-fn find_node_and_save(nodes: Vec<Node>, name: &str) -> Option<Node> {
-    if let Some(node) = SAVED_NODES.clone().lock().unwrap().get(name) {
-        return Some(node.clone());
+fn find_node_and_save(
+    nodes: &[Node],
+    name: &str,
+    saved_nodes: &Memo<String, Node>,
+) -> Option<Node> {
+    if let Some(node) = saved_nodes.get(name) {
+        return Some(node);
     }
 
     let node = nodes
         .par_iter()
-        .progress()
         .find_first(|node| node.current == name)
         .cloned();
 
     if let Some(node) = node.clone() {
-        SAVED_NODES
-            .clone()
-            .lock()
-            .unwrap()
-            .insert(name.to_string(), node);
+        saved_nodes.insert(name.to_string(), node);
     }
 
     node
@@ -94,10 +97,14 @@ fn is_node_ending_with(node: Node, ending: char) -> bool {
     node.current.ends_with(ending)
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let (instructions, nodes) = parse_input(input);
+pub fn part_one(network: &Network) -> Option<u32> {
+    let Network {
+        instructions,
+        nodes,
+    } = network;
+    let saved_nodes = Memo::new();
 
-    let mut current = find_node_and_save(nodes.clone(), "AAA").unwrap();
+    let mut current = find_node_and_save(nodes, "AAA", &saved_nodes).unwrap();
 
     let mut index = 0;
     let mut count = 0;
@@ -109,10 +116,10 @@ pub fn part_one(input: &str) -> Option<u32> {
 
         match instruction {
             Instruction::Left => {
-                current = find_node_and_save(nodes.clone(), &current.left).unwrap();
+                current = find_node_and_save(nodes, &current.left, &saved_nodes).unwrap();
             }
             Instruction::Right => {
-                current = find_node_and_save(nodes.clone(), &current.right).unwrap();
+                current = find_node_and_save(nodes, &current.right, &saved_nodes).unwrap();
             }
         }
 
@@ -126,8 +133,12 @@ pub fn part_one(input: &str) -> Option<u32> {
     Some(count)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let (instructions, nodes) = parse_input(input);
+pub fn part_two(network: &Network) -> Option<u64> {
+    let Network {
+        instructions,
+        nodes,
+    } = network;
+    let saved_nodes = Memo::new();
     let currents = find_nodes_ending_with(nodes.clone(), 'A');
 
     let path_to_z: Vec<usize> = currents
@@ -135,7 +146,7 @@ pub fn part_two(input: &str) -> Option<u64> {
         .cloned()
         .collect::<Vec<Node>>()
         .par_iter()
-        .progress()
+        .progress_with(progress::bar(currents.len() as u64))
         .map(|node| {
             let mut iteration = 0;
             let mut index = 0;
@@ -147,10 +158,10 @@ pub fn part_two(input: &str) -> Option<u64> {
                 iteration += 1;
                 match instruction {
                     Instruction::Left => {
-                        node = find_node_and_save(nodes.clone(), &node.left).unwrap();
+                        node = find_node_and_save(nodes, &node.left, &saved_nodes).unwrap();
                     }
                     Instruction::Right => {
-                        node = find_node_and_save(nodes.clone(), &node.right).unwrap();
+                        node = find_node_and_save(nodes, &node.right, &saved_nodes).unwrap();
                     }
                 }
 
@@ -165,31 +176,32 @@ pub fn part_two(input: &str) -> Option<u64> {
         })
         .collect();
 
-    let lcm = path_to_z
-        .into_iter()
-        .progress()
-        .fold(1, |acc, x| num::integer::lcm(acc, x));
+    let lcm = path_to_z.into_iter().fold(1, num::integer::lcm);
 
     Some(lcm.try_into().unwrap())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+#[cfg(all(test, feature = "fuzz"))]
+fn networks(rng: &mut advent_of_code::template::fuzz::Rng) -> String {
+    let len = rng.range(1, 6) as usize;
+    let instructions = rng.string("LR", len);
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 1,
-        ));
-        assert_eq!(result, Some(6));
+    // chains of nodes from an `A` node to a `Z` node that loops back, whichever way is taken.
+    let mut nodes = vec![];
+    for (chain, start, end) in [("G", "AAA", "ZZZ"), ("H", "11A", "11Z")]
+        .into_iter()
+        .take(rng.range(1, 2) as usize)
+    {
+        let chain_len = rng.range(1, 8);
+        let names: Vec<String> = std::iter::once(start.to_string())
+            .chain((0..chain_len).map(|i| format!("{chain}{i:02}")))
+            .chain(std::iter::once(end.to_string()))
+            .collect();
+        for (i, name) in names.iter().enumerate() {
+            let next = names.get(i + 1).unwrap_or(&names[1]);
+            nodes.push(format!("{name} = ({next}, {next})"));
+        }
     }
 
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
-        ));
-        assert_eq!(result, Some(6));
-    }
+    format!("{instructions}\n\n{}", nodes.join("\n"))
 }
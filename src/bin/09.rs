@@ -2,7 +2,7 @@ use rayon::iter::{
     IndexedParallelIterator, IntoParallelRefIterator, IntoParallelRefMutIterator, ParallelIterator,
 };
 
-advent_of_code::solution!(9);
+advent_of_code::solution!(9, fuzz = histories);
 
 #[derive(Debug)]
 struct History {
@@ -124,19 +124,25 @@ pub fn part_two(input: &str) -> Option<i32> {
     Some(res)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(114));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(2));
-    }
+#[cfg(all(test, feature = "fuzz"))]
+fn histories(rng: &mut advent_of_code::template::fuzz::Rng) -> String {
+    (0..rng.range(1, 5))
+        .map(|_| {
+            // AoC histories are polynomials, their differences reach zero.
+            let coefficients: Vec<i64> = (0..rng.range(1, 4))
+                .map(|_| rng.range(0, 10) as i64 - 5)
+                .collect();
+            (0..rng.range(3, 21) as i64)
+                .map(|x| {
+                    coefficients
+                        .iter()
+                        .rev()
+                        .fold(0, |value, coefficient| value * x + coefficient)
+                        .to_string()
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
 }
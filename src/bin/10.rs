@@ -0,0 +1,261 @@
+use std::collections::HashSet;
+
+use advent_of_code::template::viz::{self, Color, Frame};
+
+advent_of_code::solution!(10);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    fn opposite(self) -> Self {
+        match self {
+            Direction::North => Direction::South,
+            Direction::East => Direction::West,
+            Direction::South => Direction::North,
+            Direction::West => Direction::East,
+        }
+    }
+
+    fn offset(self) -> (isize, isize) {
+        match self {
+            Direction::North => (0, -1),
+            Direction::East => (1, 0),
+            Direction::South => (0, 1),
+            Direction::West => (-1, 0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tile {
+    Pipe(Direction, Direction),
+    Ground,
+    Start,
+}
+
+impl Tile {
+    fn from_char(c: char) -> Tile {
+        match c {
+            '|' => Tile::Pipe(Direction::North, Direction::South),
+            '-' => Tile::Pipe(Direction::East, Direction::West),
+            'L' => Tile::Pipe(Direction::North, Direction::East),
+            'J' => Tile::Pipe(Direction::North, Direction::West),
+            '7' => Tile::Pipe(Direction::South, Direction::West),
+            'F' => Tile::Pipe(Direction::East, Direction::South),
+            'S' => Tile::Start,
+            '.' => Tile::Ground,
+            _ => panic!("Invalid character: {}", c),
+        }
+    }
+
+    fn connects(&self, direction: Direction) -> bool {
+        match self {
+            Tile::Pipe(a, b) => *a == direction || *b == direction,
+            _ => false,
+        }
+    }
+
+    /// The other end of a pipe entered through `from`.
+    fn exit(&self, from: Direction) -> Direction {
+        match self {
+            Tile::Pipe(a, b) if *a == from => *b,
+            Tile::Pipe(a, b) if *b == from => *a,
+            _ => panic!("Pipe {:?} cannot be entered from {:?}", self, from),
+        }
+    }
+}
+
+type Position = (usize, usize);
+
+#[derive(Debug, Clone)]
+struct Maze {
+    grid: Vec<Vec<Tile>>,
+    start: Position,
+}
+
+impl Maze {
+    /// Parses the maze and replaces the start tile by the only pipe that connects to both of its neighbours.
+    pub fn new(input: &str) -> Self {
+        let grid: Vec<Vec<Tile>> = input
+            .lines()
+            .map(|line| line.chars().map(Tile::from_char).collect())
+            .collect();
+
+        let start = grid
+            .iter()
+            .enumerate()
+            .find_map(|(y, row)| {
+                row.iter()
+                    .position(|tile| *tile == Tile::Start)
+                    .map(|x| (x, y))
+            })
+            .expect("Maze has no start tile");
+
+        let mut maze = Maze { grid, start };
+
+        let connected: Vec<Direction> = Direction::ALL
+            .into_iter()
+            .filter(|&direction| {
+                maze.neighbour(start, direction)
+                    .is_some_and(|(x, y)| maze.grid[y][x].connects(direction.opposite()))
+            })
+            .collect();
+
+        assert_eq!(
+            connected.len(),
+            2,
+            "Start tile must connect to exactly two pipes, found {:?}",
+            connected
+        );
+
+        maze.grid[start.1][start.0] = Tile::Pipe(connected[0], connected[1]);
+
+        maze
+    }
+
+    fn neighbour(&self, (x, y): Position, direction: Direction) -> Option<Position> {
+        let (dx, dy) = direction.offset();
+        let x = x.checked_add_signed(dx)?;
+        let y = y.checked_add_signed(dy)?;
+
+        if y < self.grid.len() && x < self.grid[y].len() {
+            Some((x, y))
+        } else {
+            None
+        }
+    }
+
+    /// Follows the pipes from the start tile back to it and returns every tile of the loop in order.
+    pub fn trace_loop(&self) -> Vec<Position> {
+        let Tile::Pipe(mut heading, _) = self.grid[self.start.1][self.start.0] else {
+            panic!("Start tile was not resolved to a pipe");
+        };
+
+        let mut path = vec![self.start];
+        let mut current = self.neighbour(self.start, heading).unwrap();
+
+        while current != self.start {
+            path.push(current);
+            heading = self.grid[current.1][current.0].exit(heading.opposite());
+            current = self.neighbour(current, heading).unwrap();
+        }
+
+        viz::emit_steps(path.len(), |step| self.frame(&path[..step], Color::Yellow));
+
+        path
+    }
+
+    /// The maze with the tiles of `path` highlighted, see `--viz`.
+    fn frame(&self, path: &[Position], color: Color) -> Frame {
+        Frame::from_grid(&self.grid, |tile| match tile {
+            Tile::Pipe(Direction::North, Direction::South) => '│',
+            Tile::Pipe(Direction::East, Direction::West) => '─',
+            Tile::Pipe(Direction::North, Direction::East) => '└',
+            Tile::Pipe(Direction::North, Direction::West) => '┘',
+            Tile::Pipe(Direction::South, Direction::West) => '┐',
+            Tile::Pipe(Direction::East, Direction::South) => '┌',
+            Tile::Start => 'S',
+            _ => '.',
+        })
+        .highlight_all(path.iter().copied(), color)
+    }
+
+    /// Counts enclosed tiles with the shoelace formula for the loop area and Pick's theorem for the interior points.
+    pub fn interior_by_shoelace(&self, path: &[Position]) -> usize {
+        let twice_area: isize = path
+            .iter()
+            .zip(path.iter().cycle().skip(1))
+            .map(|(&(x1, y1), &(x2, y2))| (x1 * y2) as isize - (x2 * y1) as isize)
+            .sum();
+
+        // Pick's theorem: A = i + b/2 - 1
+        (twice_area.unsigned_abs() - path.len()) / 2 + 1
+    }
+
+    /// Counts enclosed tiles by scanning each row and toggling "inside" whenever a pipe with a northern
+    /// connection is crossed.
+    pub fn interior_by_scanline(&self, path: &[Position]) -> usize {
+        let on_loop: HashSet<&Position> = path.iter().collect();
+
+        self.grid
+            .iter()
+            .enumerate()
+            .map(|(y, row)| {
+                let mut inside = false;
+
+                row.iter()
+                    .enumerate()
+                    .filter(|&(x, tile)| {
+                        if on_loop.contains(&(x, y)) {
+                            if tile.connects(Direction::North) {
+                                inside = !inside;
+                            }
+                            return false;
+                        }
+
+                        inside
+                    })
+                    .count()
+            })
+            .sum()
+    }
+}
+
+pub fn part_one(input: &str) -> Option<u32> {
+    let maze = Maze::new(input);
+
+    Some((maze.trace_loop().len() / 2) as u32)
+}
+
+pub fn part_two(input: &str) -> Option<u32> {
+    let maze = Maze::new(input);
+    let path = maze.trace_loop();
+    let interior = maze.interior_by_shoelace(&path);
+
+    debug_assert_eq!(interior, maze.interior_by_scanline(&path));
+
+    Some(interior as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_start_inference() {
+        let maze = Maze::new(&advent_of_code::template::read_file_part(
+            "examples", DAY, 2,
+        ));
+        assert_eq!(maze.start, (0, 2));
+        assert_eq!(
+            maze.grid[2][0],
+            Tile::Pipe(Direction::East, Direction::South)
+        );
+    }
+
+    #[test]
+    fn test_interior_methods_agree() {
+        for (example, expected) in [(3, 4), (4, 4), (5, 8), (6, 10)] {
+            let maze = Maze::new(&advent_of_code::template::read_file_part(
+                "examples", DAY, example,
+            ));
+            let path = maze.trace_loop();
+
+            assert_eq!(maze.interior_by_shoelace(&path), expected);
+            assert_eq!(maze.interior_by_scanline(&path), expected);
+        }
+    }
+}
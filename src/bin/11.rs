@@ -1,11 +1,19 @@
+use advent_of_code::template::params::Param;
+use advent_of_code::template::viz::{self, Color, Frame};
 use itertools::iproduct;
 use rayon::iter::{
     IndexedParallelIterator, IntoParallelIterator, IntoParallelRefIterator,
     IntoParallelRefMutIterator, ParallelIterator,
 };
-use tracing::{debug, info};
+use tracing::{debug, info, Span};
 
-advent_of_code::solution!(11);
+advent_of_code::solution!(11, parse = parse_input, params = [EXPANSION]);
+
+const EXPANSION: Param<usize> = Param::new(
+    "expansion",
+    1_000_000,
+    "How many times wider empty rows and columns are in part two.",
+);
 
 #[derive(Debug, Clone, Copy)]
 enum Tile {
@@ -15,10 +23,7 @@ enum Tile {
 
 impl Tile {
     fn is_galaxy(&self) -> bool {
-        match self {
-            Self::Galaxy(_) => true,
-            _ => false,
-        }
+        matches!(self, Self::Galaxy(_))
     }
 
     fn get_galaxy(&self) -> i64 {
@@ -55,26 +60,22 @@ impl Expansion {
 }
 
 #[derive(Debug, Clone)]
-struct Map {
-    pub grid: Grid,
-    pub has_expanded: bool,
-    pub galaxy_numbers: i64,
-    pub expansion: Expansion,
-    pub extender: usize,
-    pub is_part1: bool,
+pub struct Map {
+    grid: Grid,
+    has_expanded: bool,
+    galaxy_numbers: i64,
+    expansion: Expansion,
 }
 
 impl Map {
-    pub fn new(input: &str, extender: usize, is_part1: bool) -> Self {
-        let (grid, galaxy_numbers) = parse_input(input);
+    pub fn new(input: &str) -> Self {
+        let (grid, galaxy_numbers) = parse_grid(input);
 
         Map {
             grid,
             has_expanded: false,
             galaxy_numbers,
             expansion: Expansion::new(),
-            extender,
-            is_part1,
         }
     }
 
@@ -90,9 +91,9 @@ impl Map {
             .enumerate()
             .filter_map(|(ind, row)| {
                 if row.par_iter().all(|tile| !tile.is_galaxy()) {
-                    return Some(ind);
+                    Some(ind)
                 } else {
-                    return None;
+                    None
                 }
             })
             .collect();
@@ -131,10 +132,12 @@ impl Map {
             .unwrap()
     }
 
+    /// Extra rows and columns crossed between two galaxies when each empty line is `extender` lines wide.
     fn get_expansions_multiplier(
         &self,
         fst: (usize, usize),
         snd: (usize, usize),
+        extender: usize,
     ) -> (usize, usize) {
         let (min_x, max_x) = if fst.0 < snd.0 {
             (fst.0, snd.0)
@@ -164,21 +167,18 @@ impl Map {
             .filter(|&value| value >= min_x && value <= max_x)
             .count();
 
-        if self.is_part1 {
-            return (multiplier_x, multiplier_y);
-        }
-
         (
-            (multiplier_x * self.extender).abs_diff(multiplier_x),
-            (multiplier_y * self.extender).abs_diff(multiplier_y),
+            (multiplier_x * extender).abs_diff(multiplier_x),
+            (multiplier_y * extender).abs_diff(multiplier_y),
         )
     }
 
-    fn get_distance(&self, gal_1: i64, gal_2: i64) -> i64 {
+    fn get_distance(&self, gal_1: i64, gal_2: i64, extender: usize) -> i64 {
         let (x1, y1) = self.get_galaxy_location(gal_1);
         let (x2, y2) = self.get_galaxy_location(gal_2);
 
-        let (multiplier_x, multiplier_y) = self.get_expansions_multiplier((x1, y1), (x2, y2));
+        let (multiplier_x, multiplier_y) =
+            self.get_expansions_multiplier((x1, y1), (x2, y2), extender);
 
         debug!(
             "Distance between {} ({x1},{y1}) and {} ({x2},{y2}) with multiplier ({multiplier_x},{multiplier_y}) is {}",
@@ -190,15 +190,19 @@ impl Map {
         (((x1).abs_diff(x2) + multiplier_x) + (y1.abs_diff(y2) + multiplier_y)) as i64
     }
 
-    pub fn shortest_paths(&self) -> Vec<i64> {
+    pub fn shortest_paths(&self, extender: usize) -> Vec<i64> {
         let all_galaxy_pairs: Vec<(i64, i64)> =
             iproduct!(1..self.galaxy_numbers, 2..=self.galaxy_numbers)
                 .filter(|&(a, b)| a < b)
                 .collect();
 
+        // rayon workers log under the span of the part.
+        let span = Span::current();
         let res: Vec<i64> = all_galaxy_pairs
             .par_iter()
-            .map(|(galaxy_1, galaxy_2)| self.get_distance(*galaxy_1 as i64, *galaxy_2 as i64))
+            .map(|(galaxy_1, galaxy_2)| {
+                span.in_scope(|| self.get_distance(*galaxy_1, *galaxy_2, extender))
+            })
             .collect();
 
         debug!("all paths {:?}", res);
@@ -206,19 +210,33 @@ impl Map {
         res
     }
 
-    // pub fn print(&self) {
-    //     self.grid.par_iter().enumerate().for_each(|(_, row)| {
-    //         row.par_iter().enumerate().for_each(|(_, tile)| match tile {
-    //             Tile::Galaxy(i) => print!("{i}"),
-    //             Tile::Point => print!("."),
-    //         });
+    /// The universe with its empty rows and columns highlighted, see `--viz`.
+    pub fn frame(&self) -> Frame {
+        let width = self.grid[0].len();
+        let height = self.grid.len();
 
-    //         println!("");
-    //     });
-    // }
+        Frame::from_grid(&self.grid, |tile| match tile {
+            Tile::Galaxy(_) => '#',
+            Tile::Point => '.',
+        })
+        .highlight_all(
+            self.expansion
+                .row
+                .iter()
+                .flat_map(|&y| (0..width).map(move |x| (x, y))),
+            Color::Blue,
+        )
+        .highlight_all(
+            self.expansion
+                .col
+                .iter()
+                .flat_map(|&x| (0..height).map(move |y| (x, y))),
+            Color::Blue,
+        )
+    }
 }
 
-fn parse_input(input: &str) -> (Grid, i64) {
+fn parse_grid(input: &str) -> (Grid, i64) {
     let mut counter = 0;
 
     let grid = input
@@ -229,7 +247,7 @@ fn parse_input(input: &str) -> (Grid, i64) {
                     '.' => Tile::Point,
                     '#' => {
                         counter += 1;
-                        return Tile::Galaxy(counter);
+                        Tile::Galaxy(counter)
                     }
                     _ => panic!("Invalid character: {}", c),
                 })
@@ -240,57 +258,25 @@ fn parse_input(input: &str) -> (Grid, i64) {
     (grid, counter)
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let mut map = Map::new(input, 1, true);
+/// Parses and expands the universe once, the parts only differ in how wide empty lines are.
+pub fn parse_input(input: &str) -> Map {
+    let mut map = Map::new(input);
 
     map.expand_with_expansion();
 
-    let res = map.shortest_paths();
-
-    Some(res.par_iter().sum::<i64>() as u64)
+    map
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let mut map = Map::new(input, 1000000, false); //100 for test part 2
-
-    map.expand_with_expansion();
+pub fn part_one(map: &Map) -> Option<u64> {
+    viz::emit(|| map.frame());
 
-    let res = map.shortest_paths();
+    let res = map.shortest_paths(2);
 
     Some(res.par_iter().sum::<i64>() as u64)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use tracing::Level;
-    use tracing_subscriber::FmtSubscriber;
+pub fn part_two(map: &Map) -> Option<u64> {
+    let res = map.shortest_paths(EXPANSION.get());
 
-    #[test]
-    fn test_part_one() {
-        let subscriber = FmtSubscriber::builder()
-            .with_max_level(Level::TRACE)
-            .pretty()
-            .finish();
-
-        tracing::subscriber::set_global_default(subscriber)
-            .expect("setting default subscriber failed");
-
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(374));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let subscriber = FmtSubscriber::builder()
-            .with_max_level(Level::TRACE)
-            .pretty()
-            .finish();
-
-        tracing::subscriber::set_global_default(subscriber)
-            .expect("setting default subscriber failed");
-
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(8410));
-    }
+    Some(res.par_iter().sum::<i64>() as u64)
 }
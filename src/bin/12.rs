@@ -1,147 +1,36 @@
+use advent_of_code::solvers::nonogram::{self, Cell};
+use advent_of_code::template::{params::Param, progress};
 use indicatif::ParallelProgressIterator;
 use itertools::Itertools;
-use once_cell::sync::Lazy;
-use rayon::iter::once;
 use rayon::prelude::*;
-use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
 use tracing::debug;
 
-advent_of_code::solution!(12);
+advent_of_code::solution!(12, parse = parse_input, params = [UNFOLD]);
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-enum Spring {
-    Operationnal,
-    Damaged,
-    Unknown,
-}
-
-impl Spring {
-    pub fn is_unknown(&self) -> bool {
-        match self {
-            Spring::Unknown => true,
-            _ => false,
-        }
-    }
-
-    pub fn is_operationnal(&self) -> bool {
-        match self {
-            Spring::Operationnal => true,
-            _ => false,
-        }
-    }
-
-    pub fn is_damaged(&self) -> bool {
-        match self {
-            Spring::Damaged => true,
-            _ => false,
-        }
-    }
-}
+const UNFOLD: Param<usize> = Param::new(
+    "unfold",
+    5,
+    "How many copies of each record part two joins together.",
+);
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-struct Record {
-    pub condition: Vec<Spring>,
+pub struct Record {
+    pub condition: Vec<Cell>,
     pub damaged: Vec<usize>,
 }
 
 impl Record {
-    pub fn new(condition: Vec<Spring>, damaged: Vec<usize>) -> Self {
+    pub fn new(condition: Vec<Cell>, damaged: Vec<usize>) -> Self {
         Self { condition, damaged }
     }
 
-    fn _get_condition_combination(&self) -> (Vec<usize>, Vec<Vec<Spring>>) {
-        let unknows = self
-            .condition
-            .par_iter()
-            .enumerate()
-            .filter(|(_, spring)| spring.is_unknown())
-            .map(|(i, _)| i)
-            .collect::<Vec<usize>>();
-
-        let result = Arc::new(Mutex::new(vec![]));
-        let cond = Mutex::new(self.condition.clone());
-
-        (0..2usize.pow(unknows.len() as u32))
-            .into_par_iter()
-            .for_each(|i| {
-                let mut combination = cond.lock().unwrap().clone();
-                let mut j = 0;
-                for k in unknows.iter() {
-                    combination[*k] = if i & (1 << j) != 0 {
-                        Spring::Operationnal
-                    } else {
-                        Spring::Damaged
-                    };
-                    j += 1;
-                }
-                result.clone().lock().unwrap().push(combination);
-            });
-
-        (
-            self.damaged.clone(),
-            result.clone().lock().unwrap().to_vec(),
-        )
+    pub fn arrangements(&self, unfold: usize) -> u64 {
+        let (condition, damaged) = nonogram::unfold(&self.condition, &self.damaged, unfold);
+        nonogram::count_arrangements(&condition, &damaged)
     }
 }
 
-fn count(condition: Vec<Spring>, damaged: Vec<usize>) -> usize {
-    if condition.is_empty() {
-        if damaged.is_empty() {
-            return 1;
-        }
-        return 0;
-    }
-
-    if damaged.is_empty() {
-        if condition.iter().any(|s| s.is_damaged()) {
-            return 0;
-        }
-        return 1;
-    }
-
-    let key = (condition.clone(), damaged.clone());
-
-    if let Some(cached) = SAVED_RECORDS.lock().unwrap().get(&key) {
-        return *cached;
-    }
-
-    let mut result = 0;
-
-    if condition[0].is_operationnal() || condition[0].is_unknown() {
-        let mut cond = condition.clone();
-        cond.remove(0);
-
-        result += count(cond, damaged.clone());
-    }
-
-    if condition[0].is_damaged() || condition[0].is_unknown() {
-        if damaged[0] <= condition.len()
-            && condition
-                .iter()
-                .take(damaged[0])
-                .all(|spring| !spring.is_operationnal())
-            && (damaged[0] == condition.len() || !condition[damaged[0]].is_damaged())
-        {
-            let cond = condition
-                .iter()
-                .skip(damaged[0] + 1)
-                .cloned()
-                .collect::<Vec<Spring>>();
-
-            let mut dmg = damaged.clone();
-            dmg.remove(0);
-
-            result += count(cond, dmg);
-        }
-    }
-
-    SAVED_RECORDS.lock().unwrap().insert(key, result);
-
-    result
-}
-
-fn parse_input(input: &str) -> Vec<Record> {
+pub fn parse_input(input: &str) -> Vec<Record> {
     input
         .par_lines()
         .map(|line| {
@@ -151,12 +40,8 @@ fn parse_input(input: &str) -> Vec<Record> {
                 .map(|(s1, s2)| {
                     (
                         s1.chars()
-                            .map(|c| match c {
-                                '.' => Spring::Operationnal,
-                                '#' => Spring::Damaged,
-                                _ => Spring::Unknown,
-                            })
-                            .collect::<Vec<Spring>>(),
+                            .map(|c| Cell::from_char(c).unwrap_or(Cell::Unknown))
+                            .collect::<Vec<Cell>>(),
                         s2.split(",")
                             .flat_map(|s| s.parse::<usize>())
                             .collect::<Vec<usize>>(),
@@ -169,181 +54,175 @@ fn parse_input(input: &str) -> Vec<Record> {
         .collect()
 }
 
-//Old version
-fn _get_all_valid_combinations(records: Vec<Record>) -> Vec<Vec<Spring>> {
-    let result = Arc::new(Mutex::new(vec![]));
-
-    records
+pub fn part_one(records: &[Record]) -> Option<u64> {
+    let res = records
         .par_iter()
-        .progress()
-        .map(|r| r._get_condition_combination())
-        .for_each(|(damaged, combinations)| {
-            combinations
-                .par_iter()
-                .filter(|c| _is_valid_combination(c.to_vec(), damaged.clone()))
-                .for_each(|c| result.clone().lock().unwrap().push(c.clone()));
-        });
-
-    result.clone().lock().unwrap().clone()
-}
-
-fn _is_valid_combination(condition: Vec<Spring>, damaged: Vec<usize>) -> bool {
-    let mut current_damaged_ind = 0;
-    let mut is_counting_damaged = false;
-    let mut counted_damaged = 0;
-    let mut current = 0;
-
-    let mut ite = condition.iter();
-
-    while let Some(spring) = ite.next() {
-        current += 1;
-
-        match spring {
-            Spring::Damaged => {
-                if !is_counting_damaged {
-                    is_counting_damaged = true;
-                }
-
-                counted_damaged += 1;
-            }
-            Spring::Operationnal => {
-                if is_counting_damaged {
-                    is_counting_damaged = false;
-
-                    if current_damaged_ind < damaged.len() {
-                        if counted_damaged != damaged[current_damaged_ind] {
-                            return false;
-                        }
-                    }
-
-                    if current == condition.len() {
-                        break;
-                    }
+        .progress_with(progress::bar(records.len() as u64))
+        .map(|rec| rec.arrangements(1))
+        .collect::<Vec<u64>>();
 
-                    current_damaged_ind += 1;
-
-                    counted_damaged = 0;
-                }
-            }
-            _ => {}
-        }
-    }
-
-    if let Some(lst) = condition.clone().last() {
-        if let Some(pre_last) = condition.clone().get(condition.len() - 2) {
-            if lst.is_operationnal() && !pre_last.is_damaged() {
-                current_damaged_ind -= 1;
-            }
-
-            if lst.is_damaged() {
-                if current_damaged_ind < damaged.len() {
-                    if counted_damaged != damaged[current_damaged_ind] {
-                        return false;
-                    }
-                }
-            }
-        }
-    }
-
-    if current_damaged_ind == damaged.len() - 1 && current == condition.len() {
-        debug!("is_valid [{:?}] : {:?} ", damaged, condition);
-    }
+    debug!("Count: {:?}", res);
 
-    current_damaged_ind == damaged.len() - 1 && current == condition.len()
+    Some(res.iter().sum())
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let records = parse_input(input);
+pub fn part_two(records: &[Record]) -> Option<u64> {
+    let unfold = UNFOLD.get();
 
     let res = records
         .par_iter()
-        .progress()
-        .map(|rec| count(rec.condition.clone(), rec.damaged.clone()))
-        .collect::<Vec<usize>>();
+        .progress_with(progress::bar(records.len() as u64))
+        .map(|rec| rec.arrangements(unfold))
+        .collect::<Vec<u64>>();
 
     debug!("Count: {:?}", res);
 
-    Some(res.iter().sum::<usize>() as u32)
+    Some(res.iter().sum())
 }
 
-fn extend_records(records: &mut Vec<Record>, extender: usize) {
-    records.iter_mut().for_each(|record| {
-        let ori = record.condition.clone();
-
-        let mut result_vec: Vec<_> = (0..extender - 1)
-            .into_par_iter()
-            .flat_map(|_| ori.clone().into_par_iter().chain(once(Spring::Unknown)))
-            .collect();
-
-        result_vec.extend_from_slice(&ori);
+/// The brute force of the first version, which tries every way to fill the unknown cells. Too slow for real inputs,
+/// it is the reference of both parts on small ones in tests.
+#[cfg(test)]
+mod reference {
+    use super::*;
+    use std::sync::{Arc, Mutex};
 
-        record.condition = result_vec;
-    });
+    impl Record {
+        fn get_condition_combination(&self) -> (Vec<usize>, Vec<Vec<Cell>>) {
+            let unknows = self
+                .condition
+                .par_iter()
+                .enumerate()
+                .filter(|(_, spring)| **spring == Cell::Unknown)
+                .map(|(i, _)| i)
+                .collect::<Vec<usize>>();
+
+            let result = Arc::new(Mutex::new(vec![]));
+            let cond = Mutex::new(self.condition.clone());
+
+            (0..2usize.pow(unknows.len() as u32))
+                .into_par_iter()
+                .for_each(|i| {
+                    let mut combination = cond.lock().unwrap().clone();
+                    for (j, k) in unknows.iter().enumerate() {
+                        combination[*k] = if i & (1 << j) != 0 {
+                            Cell::Empty
+                        } else {
+                            Cell::Filled
+                        };
+                    }
+                    result.clone().lock().unwrap().push(combination);
+                });
 
-    records.iter_mut().for_each(|record| {
-        let ori = record.damaged.clone();
+            (
+                self.damaged.clone(),
+                result.clone().lock().unwrap().to_vec(),
+            )
+        }
+    }
 
-        let result_vec: Vec<_> = (0..extender)
-            .into_par_iter()
-            .flat_map(|_| ori.clone())
-            .collect();
+    pub fn get_all_valid_combinations(records: Vec<Record>) -> Vec<Vec<Cell>> {
+        let result = Arc::new(Mutex::new(vec![]));
 
-        record.damaged = result_vec;
-    });
-}
+        records
+            .par_iter()
+            .progress_with(progress::bar(records.len() as u64))
+            .map(|r| r.get_condition_combination())
+            .for_each(|(damaged, combinations)| {
+                combinations
+                    .par_iter()
+                    .filter(|c| is_valid_combination(c.to_vec(), damaged.clone()))
+                    .for_each(|c| result.clone().lock().unwrap().push(c.clone()));
+            });
 
-static SAVED_RECORDS: Lazy<Arc<Mutex<HashMap<(Vec<Spring>, Vec<usize>), usize>>>> =
-    Lazy::new(|| Arc::new(Mutex::new(HashMap::new())));
+        result.clone().lock().unwrap().clone()
+    }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let mut records = parse_input(input);
+    /// Whether the runs of filled cells of a fully known condition are the damaged groups.
+    fn is_valid_combination(condition: Vec<Cell>, damaged: Vec<usize>) -> bool {
+        let runs: Vec<usize> = condition
+            .split(|cell| *cell != Cell::Filled)
+            .map(<[Cell]>::len)
+            .filter(|&len| len > 0)
+            .collect();
 
-    extend_records(&mut records, 5);
+        runs == damaged
+    }
 
-    let res = records
-        .par_iter()
-        .progress()
-        .map(|rec| count(rec.condition.clone(), rec.damaged.clone()))
-        .collect::<Vec<usize>>();
+    pub fn part_one(input: &str) -> Option<u64> {
+        Some(get_all_valid_combinations(parse_input(input)).len() as u64)
+    }
 
-    debug!("Count: {:?}", res);
+    pub fn part_two(input: &str) -> Option<u64> {
+        let unfold = UNFOLD.get();
+        let records = parse_input(input)
+            .iter()
+            .map(|record| {
+                // copies of the condition are joined by an unknown cell, unlike those of the damaged groups.
+                let condition = vec![record.condition.clone(); unfold].join(&Cell::Unknown);
+                Record::new(condition, record.damaged.repeat(unfold))
+            })
+            .collect();
 
-    Some(res.iter().sum::<usize>() as u64)
+        Some(get_all_valid_combinations(records).len() as u64)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use tracing::{warn, Level};
-    use tracing_subscriber::FmtSubscriber;
+    use advent_of_code::template::differential::Differential;
+    use advent_of_code::template::fuzz::{Generator, Rng};
 
-    #[test]
-    fn test_part_one() {
-        let subscriber = FmtSubscriber::builder()
-            .with_max_level(Level::TRACE)
-            .pretty()
-            .finish();
-
-        if let Err(e) = tracing::subscriber::set_global_default(subscriber) {
-            warn!("setting default subscriber failed: {:?}", e)
-        }
+    /// Records of a few cells, small enough for the brute force.
+    struct SmallRecords {
+        max_cells: u64,
+        max_records: u64,
+    }
 
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(21));
+    impl Generator for SmallRecords {
+        fn generate(&self, rng: &mut Rng) -> String {
+            (0..rng.range(1, self.max_records))
+                .map(|_| {
+                    let len = rng.range(1, self.max_cells) as usize;
+                    let damaged: Vec<String> = (0..rng.range(1, 3))
+                        .map(|_| rng.range(1, 3).to_string())
+                        .collect();
+                    format!("{} {}", rng.string("?.#", len), damaged.join(","))
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
     }
 
     #[test]
-    fn test_part_two() {
-        let subscriber = FmtSubscriber::builder()
-            .with_max_level(Level::TRACE)
-            .pretty()
-            .finish();
-
-        if let Err(e) = tracing::subscriber::set_global_default(subscriber) {
-            warn!("setting default subscriber failed: {:?}", e)
-        }
+    fn test_part_one_against_reference() {
+        Differential::for_day(
+            DAY,
+            1,
+            |input| part_one(&parse_input(input)),
+            reference::part_one,
+        )
+        .check(&SmallRecords {
+            max_cells: 8,
+            max_records: 3,
+        });
+    }
 
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(525152));
+    #[test]
+    fn test_part_two_against_reference() {
+        // unfolded, a record has five times its cells, too many for the brute force in the example.
+        Differential::for_day(
+            DAY,
+            2,
+            |input| part_two(&parse_input(input)),
+            reference::part_two,
+        )
+        .filter_examples(|example| example.params.contains_key("unfold"))
+        .with_cases(20)
+        .check(&SmallRecords {
+            max_cells: 3,
+            max_records: 1,
+        });
     }
 }
@@ -1,4 +1,5 @@
 mod day;
+pub mod solvers;
 pub mod template;
 
 pub use day::*;
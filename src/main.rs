@@ -1,92 +1,204 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve};
+use advent_of_code::template::commands::{
+    all, download, read, readme, scaffold, season, solve, stars, stats,
+};
 use args::{parse, AppArguments};
 
 mod args {
-    use std::process;
-
+    use advent_of_code::template::runner::RunOptions;
     use advent_of_code::Day;
+    use clap::{CommandFactory, Parser, Subcommand};
+    use clap_complete::Shell;
+    use std::path::PathBuf;
+
+    /// Solutions for Advent of Code in Rust.
+    #[derive(Parser)]
+    #[command(version, about)]
+    pub struct Cli {
+        /// Year of the puzzles, passed on to aoc-cli.
+        #[arg(long, global = true, env = "AOC_YEAR")]
+        pub year: Option<u16>,
+
+        /// Configuration file of the project [default: aoc.toml of the project root].
+        #[arg(long, global = true, env = "AOC_CONFIG")]
+        pub config: Option<PathBuf>,
+
+        #[command(subcommand)]
+        pub command: AppArguments,
+    }
 
+    #[derive(Subcommand)]
     pub enum AppArguments {
+        /// Download the input and puzzle description of a day via aoc-cli.
         Download {
+            /// Day of advent, between 1 and 25.
             day: Day,
         },
+        /// Print the puzzle description of a day in the terminal via aoc-cli.
         Read {
+            /// Day of advent, between 1 and 25.
             day: Day,
         },
+        /// Create the solution, input and example files of a day.
         Scaffold {
+            /// Day of advent, between 1 and 25.
             day: Day,
         },
+        /// Download and scaffold every unlocked day that is not scaffolded yet.
+        Season,
+        /// Run the solution of a day against its real input.
         Solve {
+            /// Day of advent, between 1 and 25.
             day: Day,
+            /// Run an optimized build.
+            #[arg(long)]
             release: bool,
-            time: bool,
-            submit: Option<u8>,
+            #[command(flatten)]
+            options: RunOptions,
         },
+        /// Run the solutions of all days.
         All {
+            /// Run optimized builds.
+            #[arg(long)]
             release: bool,
+            /// Benchmark the solutions, and update the README benchmarks if `--release` is set.
+            #[arg(long)]
             time: bool,
+            /// Print the changes to the README benchmarks as a diff instead of writing them.
+            #[arg(long, requires = "time")]
+            dry_run: bool,
+        },
+        /// Update the README stars table from the progress file, without network access.
+        Stars {
+            /// Day of a part to record as accepted first.
+            #[arg(requires = "part")]
+            day: Option<Day>,
+            /// Part to record as accepted.
+            #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+            part: Option<u8>,
+            /// Print the changes to the README as a diff instead of writing them.
+            #[arg(long)]
+            dry_run: bool,
         },
+        /// Update the README notes of each day and the language stats of the solutions.
+        Readme {
+            /// Print the changes to the README as a diff instead of writing them.
+            #[arg(long)]
+            dry_run: bool,
+        },
+        /// Summarise all solutions: answers, timings, lines of code and the days over budget.
+        Stats {
+            /// Run optimized builds.
+            #[arg(long)]
+            release: bool,
+            /// Benchmark the solutions instead of running them once.
+            #[arg(long)]
+            time: bool,
+            /// Print the report as markdown instead of a coloured table.
+            #[arg(long)]
+            markdown: bool,
+            /// Days taking longer than this are listed in the hall of shame, in milliseconds.
+            #[arg(long, value_name = "MS")]
+            budget: Option<u64>,
+        },
+        /// Print a shell completion script to stdout.
+        Completions { shell: Shell },
     }
 
-    pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
-        let mut args = pico_args::Arguments::from_env();
-
-        let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
-                release: args.contains("--release"),
-                time: args.contains("--time"),
-            },
-            Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
-            },
-            Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
-            },
-            Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
-            },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                time: args.contains("--time"),
-            },
-            Some(x) => {
-                eprintln!("Unknown command: {x}");
-                process::exit(1);
-            }
-            None => {
-                eprintln!("No command specified.");
-                process::exit(1);
-            }
-        };
-
-        let remaining = args.finish();
-        if !remaining.is_empty() {
-            eprintln!("Warning: unknown argument(s): {remaining:?}.");
+    pub fn parse() -> AppArguments {
+        let cli = Cli::parse();
+
+        // aoc-cli wrappers, the configuration and child processes read these from the environment.
+        if let Some(year) = cli.year {
+            std::env::set_var("AOC_YEAR", year.to_string());
+        }
+        if let Some(config) = &cli.config {
+            std::env::set_var("AOC_CONFIG", config);
         }
 
-        Ok(app_args)
+        cli.command
     }
-}
 
-fn main() {
-    match parse() {
-        Err(err) => {
-            eprintln!("Error: {err}");
-            std::process::exit(1);
+    pub fn print_completions(shell: Shell) {
+        let mut command = Cli::command();
+        let name = command.get_name().to_string();
+        clap_complete::generate(shell, &mut command, name, &mut std::io::stdout());
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::{AppArguments, Cli};
+        use clap::{CommandFactory, Parser};
+        use std::path::PathBuf;
+
+        #[test]
+        fn verify_cli() {
+            Cli::command().debug_assert();
         }
-        Ok(args) => match args {
-            AppArguments::All { release, time } => all::handle(release, time),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day } => scaffold::handle(day),
-            AppArguments::Solve {
+
+        #[test]
+        fn parses_solve() {
+            let cli =
+                Cli::try_parse_from(["aoc", "solve", "1", "--release", "--submit", "2"]).unwrap();
+
+            let AppArguments::Solve {
                 day,
                 release,
-                time,
-                submit,
-            } => solve::handle(day, release, time, submit),
-        },
+                options,
+            } = cli.command
+            else {
+                panic!("expected the solve command");
+            };
+
+            assert_eq!(day, 1);
+            assert!(release);
+            assert!(!options.time);
+            assert_eq!(options.submit, Some(2));
+        }
+
+        #[test]
+        fn rejects_invalid_values() {
+            assert!(Cli::try_parse_from(["aoc", "solve", "1", "--submit", "3"]).is_err());
+            assert!(Cli::try_parse_from(["aoc", "solve", "26"]).is_err());
+            assert!(Cli::try_parse_from(["aoc", "unknown"]).is_err());
+            assert!(Cli::try_parse_from(["aoc", "stars", "5"]).is_err());
+            assert!(Cli::try_parse_from(["aoc", "stars", "5", "3"]).is_err());
+        }
+
+        #[test]
+        fn leaves_config_to_project_root() {
+            let cli = Cli::try_parse_from(["aoc", "stars"]).unwrap();
+            assert_eq!(cli.config, None);
+
+            let cli = Cli::try_parse_from(["aoc", "stars", "--config", "other.toml"]).unwrap();
+            assert_eq!(cli.config, Some(PathBuf::from("other.toml")));
+        }
+    }
+}
+
+fn main() {
+    match parse() {
+        AppArguments::All {
+            release,
+            time,
+            dry_run,
+        } => all::handle(release, time, dry_run),
+        AppArguments::Download { day } => download::handle(day),
+        AppArguments::Read { day } => read::handle(day),
+        AppArguments::Scaffold { day } => scaffold::handle(day),
+        AppArguments::Season => season::handle(),
+        AppArguments::Solve {
+            day,
+            release,
+            options,
+        } => solve::handle(day, release, &options),
+        AppArguments::Stars { day, part, dry_run } => stars::handle(day.zip(part), dry_run),
+        AppArguments::Readme { dry_run } => readme::handle(dry_run),
+        AppArguments::Stats {
+            release,
+            time,
+            markdown,
+            budget,
+        } => stats::handle(release, time, markdown, budget),
+        AppArguments::Completions { shell } => args::print_completions(shell),
     };
 }
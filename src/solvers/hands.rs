@@ -0,0 +1,314 @@
+/// Module that ranks hands of cards, as in "Camel Cards".
+/// A [`RankingEngine`] combines a card ordering, an optional wildcard and a tie-breaking strategy.
+use std::cmp::{Ordering, Reverse};
+
+/// Ranks from weakest to strongest, with `J` as a jack.
+pub const STANDARD_RANKS: &str = "23456789TJQKA";
+
+/// Ranks from weakest to strongest, with `J` as a joker that is weaker than every other card.
+pub const JOKER_RANKS: &str = "J23456789TQKA";
+
+/// The type of a hand, ordered from weakest to strongest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HandType {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    FullHouse,
+    FourOfAKind,
+    FiveOfAKind,
+}
+
+impl HandType {
+    /// Classifies a hand from the sizes of its groups of equal cards, sorted from largest to smallest.
+    fn from_group_sizes(sizes: &[usize]) -> Self {
+        match sizes {
+            [largest, ..] if *largest >= 5 => HandType::FiveOfAKind,
+            [4, ..] => HandType::FourOfAKind,
+            [3, 2, ..] => HandType::FullHouse,
+            [3, ..] => HandType::ThreeOfAKind,
+            [2, 2, ..] => HandType::TwoPair,
+            [2, ..] => HandType::OnePair,
+            _ => HandType::HighCard,
+        }
+    }
+}
+
+/// Maps card characters to their strength (`0` being the weakest).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CardOrdering {
+    ranks: Vec<char>,
+}
+
+impl CardOrdering {
+    /// Creates an ordering from the card characters listed from weakest to strongest.
+    #[must_use]
+    pub fn new(ranks: &str) -> Self {
+        Self {
+            ranks: ranks.chars().collect(),
+        }
+    }
+
+    /// Strength of a card, or [`None`] if the card is not part of this ordering.
+    #[must_use]
+    pub fn strength(&self, card: char) -> Option<u8> {
+        self.ranks
+            .iter()
+            .position(|&rank| rank == card)
+            .and_then(|position| u8::try_from(position).ok())
+    }
+
+    /// Number of distinct ranks.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.ranks.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.ranks.is_empty()
+    }
+}
+
+/// How two hands of the same type are ordered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TieBreak {
+    /// Compare card strengths in the order the cards were dealt (Camel Cards).
+    #[default]
+    DealtOrder,
+    /// Compare the largest groups first, then stronger cards first (poker style).
+    /// Wildcards keep their own strength here.
+    GroupedHighCards,
+}
+
+/// Sort key of a hand: hands compare by type first, then by the tie-break strengths.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HandScore {
+    pub hand_type: HandType,
+    pub tie_break: Vec<u8>,
+}
+
+/// A configurable hand classifier.
+///
+/// ```
+/// # use advent_of_code::solvers::hands::{CardOrdering, HandType, RankingEngine, JOKER_RANKS};
+/// let engine = RankingEngine::new(CardOrdering::new(JOKER_RANKS)).with_wildcard('J');
+/// let hand = engine.parse("KTJJT").unwrap();
+/// assert_eq!(engine.hand_type(&hand), HandType::FourOfAKind);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RankingEngine {
+    ordering: CardOrdering,
+    wildcard: Option<u8>,
+    tie_break: TieBreak,
+}
+
+impl RankingEngine {
+    #[must_use]
+    pub fn new(ordering: CardOrdering) -> Self {
+        Self {
+            ordering,
+            wildcard: None,
+            tie_break: TieBreak::default(),
+        }
+    }
+
+    /// Makes `card` a wildcard that stands in for whichever rank gives the best hand type.
+    ///
+    /// # Panics
+    /// Panics if `card` is not part of the card ordering.
+    #[must_use]
+    pub fn with_wildcard(mut self, card: char) -> Self {
+        let strength = self
+            .ordering
+            .strength(card)
+            .unwrap_or_else(|| panic!("wildcard `{card}` is not part of the card ordering"));
+        self.wildcard = Some(strength);
+        self
+    }
+
+    #[must_use]
+    pub fn with_tie_break(mut self, tie_break: TieBreak) -> Self {
+        self.tie_break = tie_break;
+        self
+    }
+
+    /// Converts a hand like `"KTJJT"` into card strengths, or [`None`] if it contains an unknown card.
+    #[must_use]
+    pub fn parse(&self, hand: &str) -> Option<Vec<u8>> {
+        hand.chars().map(|c| self.ordering.strength(c)).collect()
+    }
+
+    /// Sizes of the groups of equal cards, largest first, ignoring wildcards.
+    fn group_sizes(&self, cards: &[u8]) -> Vec<usize> {
+        let mut counts = vec![0; self.ordering.len()];
+        for &card in cards {
+            if Some(card) != self.wildcard {
+                counts[usize::from(card)] += 1;
+            }
+        }
+
+        let mut sizes: Vec<usize> = counts.into_iter().filter(|&count| count > 0).collect();
+        sizes.sort_unstable_by_key(|&size| Reverse(size));
+        sizes
+    }
+
+    /// Classifies a hand. Wildcards always join the largest group, which is never worse than any other choice.
+    #[must_use]
+    pub fn hand_type(&self, cards: &[u8]) -> HandType {
+        let wildcards = cards.iter().filter(|&&c| Some(c) == self.wildcard).count();
+        let mut sizes = self.group_sizes(cards);
+
+        match sizes.first_mut() {
+            Some(largest) => *largest += wildcards,
+            None => sizes.push(wildcards),
+        }
+
+        HandType::from_group_sizes(&sizes)
+    }
+
+    /// Classifies a hand by trying every rank for every wildcard and keeping the best result.
+    /// Exponential in the number of wildcards, useful as a reference for [`RankingEngine::hand_type`].
+    #[must_use]
+    pub fn best_hand_by_substitution(&self, cards: &[u8]) -> HandType {
+        let Some(position) = cards.iter().position(|&c| Some(c) == self.wildcard) else {
+            return self.hand_type(cards);
+        };
+
+        let mut candidate = cards.to_vec();
+        (0..self.ordering.len())
+            .filter_map(|rank| u8::try_from(rank).ok())
+            .filter(|&rank| Some(rank) != self.wildcard)
+            .map(|rank| {
+                candidate[position] = rank;
+                self.best_hand_by_substitution(&candidate)
+            })
+            .max()
+            // a hand where the wildcard is the only rank can only stay as it is.
+            .unwrap_or_else(|| self.hand_type(cards))
+    }
+
+    fn tie_break(&self, cards: &[u8]) -> Vec<u8> {
+        match self.tie_break {
+            TieBreak::DealtOrder => cards.to_vec(),
+            TieBreak::GroupedHighCards => {
+                let mut sorted = cards.to_vec();
+                sorted.sort_by_cached_key(|&card| {
+                    let group = cards.iter().filter(|&&c| c == card).count();
+                    Reverse((group, card))
+                });
+                sorted
+            }
+        }
+    }
+
+    #[must_use]
+    pub fn score(&self, cards: &[u8]) -> HandScore {
+        HandScore {
+            hand_type: self.hand_type(cards),
+            tie_break: self.tie_break(cards),
+        }
+    }
+
+    #[must_use]
+    pub fn compare(&self, a: &[u8], b: &[u8]) -> Ordering {
+        self.score(a).cmp(&self.score(b))
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{CardOrdering, HandType, RankingEngine, TieBreak, JOKER_RANKS, STANDARD_RANKS};
+    use std::cmp::Ordering;
+
+    fn standard() -> RankingEngine {
+        RankingEngine::new(CardOrdering::new(STANDARD_RANKS))
+    }
+
+    fn jokers() -> RankingEngine {
+        RankingEngine::new(CardOrdering::new(JOKER_RANKS)).with_wildcard('J')
+    }
+
+    fn hand_type(engine: &RankingEngine, hand: &str) -> HandType {
+        engine.hand_type(&engine.parse(hand).unwrap())
+    }
+
+    #[test]
+    fn classifies_hands() {
+        let engine = standard();
+        assert_eq!(hand_type(&engine, "22222"), HandType::FiveOfAKind);
+        assert_eq!(hand_type(&engine, "22223"), HandType::FourOfAKind);
+        assert_eq!(hand_type(&engine, "22233"), HandType::FullHouse);
+        assert_eq!(hand_type(&engine, "22234"), HandType::ThreeOfAKind);
+        assert_eq!(hand_type(&engine, "22334"), HandType::TwoPair);
+        assert_eq!(hand_type(&engine, "22345"), HandType::OnePair);
+        assert_eq!(hand_type(&engine, "23456"), HandType::HighCard);
+        assert_eq!(hand_type(&engine, "JJJJ2"), HandType::FourOfAKind);
+    }
+
+    #[test]
+    fn classifies_hands_with_wildcards() {
+        let engine = jokers();
+        assert_eq!(hand_type(&engine, "JJJJJ"), HandType::FiveOfAKind);
+        assert_eq!(hand_type(&engine, "JJJJ2"), HandType::FiveOfAKind);
+        assert_eq!(hand_type(&engine, "T55J5"), HandType::FourOfAKind);
+        assert_eq!(hand_type(&engine, "2233J"), HandType::FullHouse);
+        assert_eq!(hand_type(&engine, "2345J"), HandType::OnePair);
+    }
+
+    #[test]
+    fn rejects_unknown_cards() {
+        assert_eq!(standard().parse("2345X"), None);
+    }
+
+    #[test]
+    fn breaks_ties_in_dealt_order() {
+        let engine = standard();
+        let a = engine.parse("KK677").unwrap();
+        let b = engine.parse("KTJJT").unwrap();
+        assert_eq!(engine.compare(&a, &b), Ordering::Greater);
+
+        let engine = jokers();
+        let a = engine.parse("JKKK2").unwrap();
+        let b = engine.parse("QQQQ2").unwrap();
+        assert_eq!(engine.compare(&a, &b), Ordering::Less);
+    }
+
+    #[test]
+    fn breaks_ties_by_grouped_high_cards() {
+        let engine = standard().with_tie_break(TieBreak::GroupedHighCards);
+        let a = engine.parse("23332").unwrap();
+        let b = engine.parse("A2A22").unwrap();
+        // both are full houses, three 3s beat three 2s even though `A` is dealt first.
+        assert_eq!(engine.compare(&a, &b), Ordering::Greater);
+        assert_eq!(engine.score(&b).tie_break, engine.parse("222AA").unwrap());
+    }
+
+    /// Every multiset of five cards (the type does not depend on the card order), with jokers resolved by
+    /// the fast rule compared against trying every rank for every joker.
+    #[test]
+    fn wildcard_resolution_matches_brute_force() {
+        fn visit(engine: &RankingEngine, hand: &mut Vec<u8>, min: u8, checked: &mut usize) {
+            if hand.len() == 5 {
+                assert_eq!(
+                    engine.hand_type(hand),
+                    engine.best_hand_by_substitution(hand),
+                    "{hand:?}"
+                );
+                *checked += 1;
+                return;
+            }
+
+            for card in min..13 {
+                hand.push(card);
+                visit(engine, hand, card, checked);
+                hand.pop();
+            }
+        }
+
+        let mut checked = 0;
+        visit(&jokers(), &mut vec![], 0, &mut checked);
+        assert_eq!(checked, 6188);
+    }
+}
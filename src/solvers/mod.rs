@@ -0,0 +1,3 @@
+pub mod hands;
+pub mod nonogram;
+pub mod ocr;
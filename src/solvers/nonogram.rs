@@ -0,0 +1,337 @@
+/// Module that counts the arrangements of runs in a partially known row.
+/// This is the "nonogram line" problem: given cells that are empty, filled or unknown and the lengths of the
+/// filled runs in order, how many ways are there to resolve the unknown cells?
+use std::fmt::Display;
+
+/// A single cell of a row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Cell {
+    Empty,
+    Filled,
+    Unknown,
+}
+
+impl Cell {
+    /// Parses the usual puzzle notation: `.` is empty, `#` is filled and `?` is unknown.
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            '.' => Some(Cell::Empty),
+            '#' => Some(Cell::Filled),
+            '?' => Some(Cell::Unknown),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn to_char(self) -> char {
+        match self {
+            Cell::Empty => '.',
+            Cell::Filled => '#',
+            Cell::Unknown => '?',
+        }
+    }
+}
+
+impl Display for Cell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_char())
+    }
+}
+
+/// Renders a row in puzzle notation, e.g. `#.#.###`.
+#[must_use]
+pub fn render(cells: &[Cell]) -> String {
+    cells.iter().map(|cell| cell.to_char()).collect()
+}
+
+/// Repeats a row `factor` times, joining the copies with an unknown cell, and repeats the runs accordingly.
+///
+/// ```
+/// # use advent_of_code::solvers::nonogram::{render, unfold, Cell};
+/// let (cells, runs) = unfold(&[Cell::Filled, Cell::Empty], &[1], 3);
+/// assert_eq!(render(&cells), "#.?#.?#.");
+/// assert_eq!(runs, vec![1, 1, 1]);
+/// ```
+#[must_use]
+pub fn unfold(cells: &[Cell], runs: &[usize], factor: usize) -> (Vec<Cell>, Vec<usize>) {
+    if factor == 0 {
+        return (vec![], vec![]);
+    }
+
+    let mut unfolded_cells = Vec::with_capacity(cells.len() * factor + factor - 1);
+
+    for i in 0..factor {
+        if i > 0 {
+            unfolded_cells.push(Cell::Unknown);
+        }
+        unfolded_cells.extend_from_slice(cells);
+    }
+
+    (unfolded_cells, runs.repeat(factor))
+}
+
+/// Counts the arrangements of `runs` in `cells`. Shorthand for [`Nonogram::new`] followed by [`Nonogram::count`].
+#[must_use]
+pub fn count_arrangements(cells: &[Cell], runs: &[usize]) -> u64 {
+    Nonogram::new(cells, runs).count()
+}
+
+/// A solved row. Construction runs an index-based dynamic program over the row once,
+/// afterwards counting is constant time and concrete arrangements can be enumerated or picked by index.
+#[derive(Debug, Clone)]
+pub struct Nonogram<'a> {
+    cells: &'a [Cell],
+    runs: &'a [usize],
+    /// `ways[i * (runs.len() + 1) + j]` holds the number of arrangements of `runs[j..]` in `cells[i..]`.
+    ways: Vec<u64>,
+}
+
+impl<'a> Nonogram<'a> {
+    #[must_use]
+    pub fn new(cells: &'a [Cell], runs: &'a [usize]) -> Self {
+        let mut nonogram = Nonogram {
+            cells,
+            runs,
+            ways: vec![0; (cells.len() + 1) * (runs.len() + 1)],
+        };
+        nonogram.solve();
+        nonogram
+    }
+
+    fn index(&self, i: usize, j: usize) -> usize {
+        i * (self.runs.len() + 1) + j
+    }
+
+    fn ways(&self, i: usize, j: usize) -> u64 {
+        self.ways[self.index(i, j)]
+    }
+
+    fn solve(&mut self) {
+        let n = self.cells.len();
+        let m = self.runs.len();
+
+        // `empty_before[i]` is the number of empty cells in `cells[..i]`, used to check a run fits in O(1).
+        let mut empty_before = vec![0; n + 1];
+        for (i, cell) in self.cells.iter().enumerate() {
+            empty_before[i + 1] = empty_before[i] + usize::from(*cell == Cell::Empty);
+        }
+
+        let end = self.index(n, m);
+        self.ways[end] = 1;
+
+        for i in (0..n).rev() {
+            for j in 0..=m {
+                let mut ways = 0;
+
+                if self.cells[i] != Cell::Filled {
+                    ways += self.ways(i + 1, j);
+                }
+
+                if self.cells[i] != Cell::Empty && j < m {
+                    let run = self.runs[j];
+                    let fits = i + run <= n
+                        && empty_before[i + run] == empty_before[i]
+                        && (i + run == n || self.cells[i + run] != Cell::Filled);
+
+                    if fits {
+                        ways += self.ways((i + run + 1).min(n), j + 1);
+                    }
+                }
+
+                let index = self.index(i, j);
+                self.ways[index] = ways;
+            }
+        }
+    }
+
+    /// Number of distinct arrangements of the runs in the row.
+    #[must_use]
+    pub fn count(&self) -> u64 {
+        self.ways(0, 0)
+    }
+
+    /// Returns the arrangement with the given index, in range `0..self.count()`.
+    /// Arrangements are ordered by placing empty cells before filled ones, so index `0` pushes every run as far right
+    /// as possible. Passing a random index samples an arrangement uniformly.
+    #[must_use]
+    pub fn nth(&self, mut index: u64) -> Option<Vec<Cell>> {
+        if index >= self.count() {
+            return None;
+        }
+
+        let n = self.cells.len();
+        let mut arrangement = Vec::with_capacity(n);
+        let (mut i, mut j) = (0, 0);
+
+        while i < n {
+            let empty_ways = if self.cells[i] == Cell::Filled {
+                0
+            } else {
+                self.ways(i + 1, j)
+            };
+
+            if index < empty_ways {
+                arrangement.push(Cell::Empty);
+                i += 1;
+                continue;
+            }
+
+            index -= empty_ways;
+
+            let run = self.runs[j];
+            arrangement.extend(std::iter::repeat_n(Cell::Filled, run));
+            if i + run < n {
+                arrangement.push(Cell::Empty);
+            }
+
+            i = (i + run + 1).min(n);
+            j += 1;
+        }
+
+        Some(arrangement)
+    }
+
+    /// Iterates over all concrete arrangements, in the order described on [`Nonogram::nth`].
+    pub fn arrangements(&self) -> impl Iterator<Item = Vec<Cell>> + '_ {
+        (0..self.count()).filter_map(|index| self.nth(index))
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{count_arrangements, render, unfold, Cell, Nonogram};
+
+    fn parse(s: &str) -> (Vec<Cell>, Vec<usize>) {
+        let (cells, runs) = s.split_once(' ').unwrap();
+        (
+            cells.chars().map(|c| Cell::from_char(c).unwrap()).collect(),
+            runs.split(',').map(|r| r.parse().unwrap()).collect(),
+        )
+    }
+
+    fn runs_of(cells: &[Cell]) -> Vec<usize> {
+        render(cells)
+            .split('.')
+            .filter(|run| !run.is_empty())
+            .map(str::len)
+            .collect()
+    }
+
+    /// Resolves every unknown cell both ways and keeps the rows whose runs match.
+    fn brute_force(cells: &[Cell], runs: &[usize]) -> u64 {
+        let unknowns: Vec<usize> = (0..cells.len())
+            .filter(|&i| cells[i] == Cell::Unknown)
+            .collect();
+
+        (0..1_u64 << unknowns.len())
+            .filter(|mask| {
+                let mut candidate = cells.to_vec();
+                for (bit, &i) in unknowns.iter().enumerate() {
+                    candidate[i] = if mask & (1 << bit) == 0 {
+                        Cell::Empty
+                    } else {
+                        Cell::Filled
+                    };
+                }
+                runs_of(&candidate) == runs
+            })
+            .count() as u64
+    }
+
+    const EXAMPLES: [(&str, u64, u64); 6] = [
+        ("???.### 1,1,3", 1, 1),
+        (".??..??...?##. 1,1,3", 4, 16384),
+        ("?#?#?#?#?#?#?#? 1,3,1,6", 1, 1),
+        ("????.#...#... 4,1,1", 1, 16),
+        ("????.######..#####. 1,6,5", 4, 2500),
+        ("?###???????? 3,2,1", 10, 506_250),
+    ];
+
+    #[test]
+    fn counts_examples() {
+        for (row, expected, _) in EXAMPLES {
+            let (cells, runs) = parse(row);
+            assert_eq!(count_arrangements(&cells, &runs), expected, "{row}");
+        }
+    }
+
+    #[test]
+    fn counts_unfolded_examples() {
+        for (row, _, expected) in EXAMPLES {
+            let (cells, runs) = parse(row);
+            let (cells, runs) = unfold(&cells, &runs, 5);
+            assert_eq!(count_arrangements(&cells, &runs), expected, "{row}");
+        }
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let rows = [
+            "???? 1",
+            "???? 1,1",
+            "#??#? 2,1",
+            "?.?.? 1,1",
+            "??#?? 5",
+            "??#?? 6",
+            "#.# 1",
+            ". 1",
+            "?? ",
+        ];
+
+        for row in rows {
+            let (cells, runs) = row.split_once(' ').unwrap();
+            let cells: Vec<Cell> = cells.chars().map(|c| Cell::from_char(c).unwrap()).collect();
+            let runs: Vec<usize> = runs.split(',').filter_map(|r| r.parse().ok()).collect();
+            assert_eq!(
+                count_arrangements(&cells, &runs),
+                brute_force(&cells, &runs),
+                "{row}"
+            );
+        }
+    }
+
+    #[test]
+    fn enumerates_valid_distinct_arrangements() {
+        let (cells, runs) = parse("?###???????? 3,2,1");
+        let nonogram = Nonogram::new(&cells, &runs);
+        let arrangements: Vec<String> = nonogram.arrangements().map(|a| render(&a)).collect();
+
+        assert_eq!(arrangements.len(), 10);
+        assert_eq!(arrangements[0], ".###....##.#");
+        assert_eq!(arrangements[9], ".###.##.#...");
+
+        for arrangement in &arrangements {
+            assert_eq!(arrangement.len(), cells.len());
+            let concrete: Vec<Cell> = arrangement
+                .chars()
+                .map(|c| Cell::from_char(c).unwrap())
+                .collect();
+            assert_eq!(runs_of(&concrete), runs);
+            assert!(cells
+                .iter()
+                .zip(&concrete)
+                .all(|(cell, c)| *cell == Cell::Unknown || cell == c));
+        }
+
+        let mut deduped = arrangements.clone();
+        deduped.sort();
+        deduped.dedup();
+        assert_eq!(deduped.len(), arrangements.len());
+    }
+
+    #[test]
+    fn nth_out_of_range() {
+        let (cells, runs) = parse("#.# 1");
+        let nonogram = Nonogram::new(&cells, &runs);
+        assert_eq!(nonogram.count(), 0);
+        assert_eq!(nonogram.nth(0), None);
+    }
+
+    #[test]
+    fn unfold_factors() {
+        let (cells, runs) = parse("#. 1");
+        assert_eq!(unfold(&cells, &runs, 0), (vec![], vec![]));
+        assert_eq!(unfold(&cells, &runs, 1), (cells.clone(), runs.clone()));
+        assert_eq!(render(&unfold(&cells, &runs, 2).0), "#.?#.");
+    }
+}
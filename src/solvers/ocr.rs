@@ -0,0 +1,254 @@
+/// Module that reads the block letters some puzzles draw as their answer.
+/// Supports the two fonts Advent of Code uses: letters 6 pixels high (about 4 wide) and letters 10 pixels high
+/// (6 wide). Lit pixels are `#` or `█`, anything else is off.
+type Glyph = Vec<Vec<bool>>;
+
+/// Letters of the 6 pixel font.
+const FONT_6: [(char, &str); 18] = [
+    ('A', ".##.|#..#|#..#|####|#..#|#..#"),
+    ('B', "###.|#..#|###.|#..#|#..#|###."),
+    ('C', ".##.|#..#|#...|#...|#..#|.##."),
+    ('E', "####|#...|###.|#...|#...|####"),
+    ('F', "####|#...|###.|#...|#...|#..."),
+    ('G', ".##.|#..#|#...|#.##|#..#|.###"),
+    ('H', "#..#|#..#|####|#..#|#..#|#..#"),
+    ('I', "###|.#.|.#.|.#.|.#.|###"),
+    ('J', "..##|...#|...#|...#|#..#|.##."),
+    ('K', "#..#|#.#.|##..|#.#.|#.#.|#..#"),
+    ('L', "#...|#...|#...|#...|#...|####"),
+    ('O', ".##.|#..#|#..#|#..#|#..#|.##."),
+    ('P', "###.|#..#|#..#|###.|#...|#..."),
+    ('R', "###.|#..#|#..#|###.|#.#.|#..#"),
+    ('S', ".###|#...|#...|.##.|...#|###."),
+    ('U', "#..#|#..#|#..#|#..#|#..#|.##."),
+    ('Y', "#...#|#...#|.#.#.|..#..|..#..|..#.."),
+    ('Z', "####|...#|..#.|.#..|#...|####"),
+];
+
+/// Letters of the 10 pixel font.
+const FONT_10: [(char, &str); 15] = [
+    (
+        'A',
+        "..##..|.#..#.|#....#|#....#|#....#|######|#....#|#....#|#....#|#....#",
+    ),
+    (
+        'B',
+        "#####.|#....#|#....#|#....#|#####.|#....#|#....#|#....#|#....#|#####.",
+    ),
+    (
+        'C',
+        ".####.|#....#|#.....|#.....|#.....|#.....|#.....|#.....|#....#|.####.",
+    ),
+    (
+        'E',
+        "######|#.....|#.....|#.....|#####.|#.....|#.....|#.....|#.....|######",
+    ),
+    (
+        'F',
+        "######|#.....|#.....|#.....|#####.|#.....|#.....|#.....|#.....|#.....",
+    ),
+    (
+        'G',
+        ".####.|#....#|#.....|#.....|#.....|#..###|#....#|#....#|#...##|.###.#",
+    ),
+    (
+        'H',
+        "#....#|#....#|#....#|#....#|######|#....#|#....#|#....#|#....#|#....#",
+    ),
+    (
+        'J',
+        "...###|....#.|....#.|....#.|....#.|....#.|....#.|#...#.|#...#.|.###..",
+    ),
+    (
+        'K',
+        "#....#|#...#.|#..#..|#.#...|##....|##....|#.#...|#..#..|#...#.|#....#",
+    ),
+    (
+        'L',
+        "#.....|#.....|#.....|#.....|#.....|#.....|#.....|#.....|#.....|######",
+    ),
+    (
+        'N',
+        "#....#|##...#|##...#|#.#..#|#.#..#|#..#.#|#..#.#|#...##|#...##|#....#",
+    ),
+    (
+        'P',
+        "#####.|#....#|#....#|#....#|#####.|#.....|#.....|#.....|#.....|#.....",
+    ),
+    (
+        'R',
+        "#####.|#....#|#....#|#....#|#####.|#..#..|#...#.|#...#.|#....#|#....#",
+    ),
+    (
+        'X',
+        "#....#|#....#|.#..#.|.#..#.|..##..|..##..|.#..#.|.#..#.|#....#|#....#",
+    ),
+    (
+        'Z',
+        "######|.....#|.....#|....#.|...#..|..#...|.#....|#.....|#.....|######",
+    ),
+];
+
+fn is_lit(c: char) -> bool {
+    matches!(c, '#' | '█')
+}
+
+fn parse_glyph(pattern: &str) -> Glyph {
+    pattern
+        .split('|')
+        .map(|row| row.chars().map(is_lit).collect())
+        .collect()
+}
+
+/// Splits rows of pixels into glyphs at empty columns, dropping empty rows at the top and bottom.
+fn split_glyphs(art: &str) -> Vec<Glyph> {
+    let rows: Vec<Vec<bool>> = art
+        .lines()
+        .map(|line| line.chars().map(is_lit).collect())
+        .skip_while(|row: &Vec<bool>| !row.contains(&true))
+        .collect();
+
+    let height = rows
+        .iter()
+        .rposition(|row| row.contains(&true))
+        .map_or(0, |last| last + 1);
+    let rows = &rows[..height];
+
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let lit = |x: usize, y: usize| rows[y].get(x).copied().unwrap_or(false);
+    let is_empty_column = |x: usize| (0..height).all(|y| !lit(x, y));
+
+    let mut glyphs = vec![];
+    let mut x = 0;
+
+    while x < width {
+        if is_empty_column(x) {
+            x += 1;
+            continue;
+        }
+
+        let start = x;
+        while x < width && !is_empty_column(x) {
+            x += 1;
+        }
+
+        glyphs.push(
+            (0..height)
+                .map(|y| (start..x).map(|x| lit(x, y)).collect())
+                .collect(),
+        );
+    }
+
+    glyphs
+}
+
+/// Letter of a glyph, trimmed like `split_glyphs` trims its output.
+fn recognise(glyph: &Glyph) -> Option<char> {
+    let font: &[(char, &str)] = match glyph.len() {
+        6 => &FONT_6,
+        10 => &FONT_10,
+        _ => return None,
+    };
+
+    font.iter().find_map(|(letter, pattern)| {
+        let mut known = parse_glyph(pattern);
+
+        // some letters are narrower than the font and padded with empty columns.
+        while known.iter().all(|row| row.last() == Some(&false)) {
+            known.iter_mut().for_each(|row| {
+                row.pop();
+            });
+        }
+        while known.iter().all(|row| row.first() == Some(&false)) {
+            known.iter_mut().for_each(|row| {
+                row.remove(0);
+            });
+        }
+
+        (known == *glyph).then_some(*letter)
+    })
+}
+
+/// Reads the letters drawn in `art`, or `None` if any of them is unknown.
+#[must_use]
+pub fn decode(art: &str) -> Option<String> {
+    let glyphs = split_glyphs(art);
+
+    if glyphs.is_empty() {
+        return None;
+    }
+
+    glyphs.iter().map(recognise).collect()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{decode, FONT_10, FONT_6};
+
+    /// Draws letters of a font next to each other, separated by `gap` empty columns.
+    fn draw(font: &[(char, &str)], word: &str, gap: usize) -> String {
+        let glyphs: Vec<Vec<&str>> = word
+            .chars()
+            .map(|c| {
+                let (_, pattern) = font.iter().find(|(letter, _)| *letter == c).unwrap();
+                pattern.split('|').collect()
+            })
+            .collect();
+
+        (0..glyphs[0].len())
+            .map(|y| {
+                glyphs
+                    .iter()
+                    .map(|glyph| glyph[y])
+                    .collect::<Vec<&str>>()
+                    .join(&".".repeat(gap))
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn decodes_every_letter() {
+        for (letter, _) in FONT_6 {
+            assert_eq!(
+                decode(&draw(&FONT_6, &letter.to_string(), 1)),
+                Some(letter.to_string())
+            );
+        }
+        for (letter, _) in FONT_10 {
+            assert_eq!(
+                decode(&draw(&FONT_10, &letter.to_string(), 2)),
+                Some(letter.to_string())
+            );
+        }
+    }
+
+    #[test]
+    fn decodes_words() {
+        assert_eq!(
+            decode(&draw(&FONT_6, "EHZRUJLO", 1)),
+            Some("EHZRUJLO".into())
+        );
+        assert_eq!(
+            decode(&draw(&FONT_10, "HJLXNZAB", 2)),
+            Some("HJLXNZAB".into())
+        );
+    }
+
+    #[test]
+    fn decodes_other_pixels() {
+        // 2022 day 10 style output with spaces and full blocks, and a trailing empty row.
+        let art = draw(&FONT_6, "PZGPKPEB", 1)
+            .replace('#', "█")
+            .replace('.', " ")
+            + "\n";
+        assert_eq!(decode(&art), Some("PZGPKPEB".into()));
+    }
+
+    #[test]
+    fn rejects_unknown_glyphs() {
+        assert_eq!(decode("#.#\n.#.\n#.#"), None);
+        assert_eq!(decode(""), None);
+        assert_eq!(decode("...\n..."), None);
+    }
+}
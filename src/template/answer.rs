@@ -0,0 +1,91 @@
+/// Module that defines what solution parts can return.
+/// Answers are displayed with `Display`, and can submit a different string, e.g. the letters read from ASCII art.
+use std::fmt::{self, Display};
+
+use crate::solvers::ocr;
+
+pub trait Answer: Display {
+    /// The string sent to Advent of Code, `None` if the answer cannot be submitted.
+    fn submission(&self) -> Option<String> {
+        Some(self.to_string())
+    }
+}
+
+macro_rules! impl_answer {
+    ($($t:ty),*) => {
+        $(impl Answer for $t {})*
+    };
+}
+
+impl_answer!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, char, String, &str);
+
+/// Block letters drawn by a puzzle, e.g. the screen of 2022 day 10.
+/// Displays as the drawing and submits the letters, read with the standard Advent of Code fonts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LetterArt(pub String);
+
+impl LetterArt {
+    /// Draws the pixels of a grid, `#` for lit and `.` for dark pixels.
+    pub fn from_pixels<R: AsRef<[bool]>>(rows: &[R]) -> Self {
+        LetterArt(
+            rows.iter()
+                .map(|row| {
+                    row.as_ref()
+                        .iter()
+                        .map(|&lit| if lit { '#' } else { '.' })
+                        .collect::<String>()
+                })
+                .collect::<Vec<String>>()
+                .join("\n"),
+        )
+    }
+}
+
+impl Display for LetterArt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Answer for LetterArt {
+    fn submission(&self) -> Option<String> {
+        ocr::decode(&self.0)
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Answer, LetterArt};
+
+    #[test]
+    fn submits_numbers_as_displayed() {
+        assert_eq!(42_u32.submission(), Some("42".into()));
+        assert_eq!((-7_i64).submission(), Some("-7".into()));
+        assert_eq!("abc".submission(), Some("abc".into()));
+    }
+
+    #[test]
+    fn submits_letters_of_art() {
+        let art = LetterArt(
+            [
+                "#..#.###.",
+                "#..#..#..",
+                "####..#..",
+                "#..#..#..",
+                "#..#..#..",
+                "#..#.###.",
+            ]
+            .join("\n"),
+        );
+
+        assert_eq!(art.to_string().lines().count(), 6);
+        assert_eq!(art.submission(), Some("HI".into()));
+        assert_eq!(LetterArt("#.#\n.#.".into()).submission(), None);
+    }
+
+    #[test]
+    fn draws_pixels() {
+        let art = LetterArt::from_pixels(&[vec![true, false], vec![false, true]]);
+        assert_eq!(art.0, "#.\n.#");
+    }
+}
@@ -4,6 +4,7 @@ use std::{
     process::{Command, Output, Stdio},
 };
 
+use crate::template::{config, data};
 use crate::Day;
 
 #[derive(Debug)]
@@ -82,18 +83,30 @@ pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandErro
     call_aoc_cli(&args)
 }
 
-fn get_input_path(day: Day) -> String {
-    format!("data/inputs/{day}.txt")
+#[must_use]
+pub fn get_input_path(day: Day) -> String {
+    get_data_path("inputs", &format!("{day}.txt"))
 }
 
-fn get_puzzle_path(day: Day) -> String {
-    format!("data/puzzles/{day}.md")
+#[must_use]
+pub fn get_puzzle_path(day: Day) -> String {
+    get_data_path("puzzles", &format!("{day}.md"))
 }
 
-fn get_year() -> Option<u16> {
+#[must_use]
+pub fn get_example_path(day: Day) -> String {
+    get_data_path("examples", &format!("{day}.txt"))
+}
+
+fn get_data_path(folder: &str, file: &str) -> String {
+    data::data_path(folder, file).to_string_lossy().into_owned()
+}
+
+/// The year from `--year` or `AOC_YEAR`, falling back to `year` in `aoc.toml`.
+pub fn get_year() -> Option<u16> {
     match std::env::var("AOC_YEAR") {
-        Ok(x) => x.parse().ok().or(None),
-        Err(_) => None,
+        Ok(x) => x.parse().ok(),
+        Err(_) => config::get().year,
     }
 }
 
@@ -1,12 +1,13 @@
-use std::io;
+use std::{fs, io};
 
+use crate::all_days;
 use crate::template::{
+    data,
     readme_benchmarks::{self, Timings},
     ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
 };
-use crate::{all_days, Day};
 
-pub fn handle(is_release: bool, is_timed: bool) {
+pub fn handle(is_release: bool, is_timed: bool, dry_run: bool) {
     let mut timings: Vec<Timings> = vec![];
 
     all_days().for_each(|day| {
@@ -17,26 +18,30 @@ pub fn handle(is_release: bool, is_timed: bool) {
         println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
         println!("------");
 
-        let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+        let output = child_commands::run_solution(day, is_timed, is_release, true).unwrap();
 
         if output.is_empty() {
             println!("Not solved.");
         } else {
-            let val = child_commands::parse_exec_time(&output, day);
+            let mut val = child_commands::parse_exec_time(&output, day);
+            val.lines_of_code = fs::read_to_string(data::bin_path(day))
+                .ok()
+                .map(|source| readme_benchmarks::count_lines_of_code(&source));
             timings.push(val);
         }
     });
 
     if is_timed {
-        let total_millis = timings.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64;
+        let total_millis = readme_benchmarks::total(&timings).as_secs_f64() * 1e3;
 
         println!("\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}");
 
         if is_release {
-            match readme_benchmarks::update(timings, total_millis) {
+            match readme_benchmarks::update(timings, dry_run) {
+                Ok(()) if dry_run => {}
                 Ok(()) => println!("Successfully updated README with benchmarks."),
-                Err(_) => {
-                    eprintln!("Failed to update readme with benchmarks.");
+                Err(e) => {
+                    eprintln!("Failed to update readme with benchmarks: {e}");
                 }
             }
         }
@@ -56,27 +61,59 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
-mod child_commands {
-    use super::{get_path_for_bin, Error};
+/// This module encapsulates interaction with these binaries, both invoking them as well as reading their timings.
+/// Binaries report in JSON, so timings arrive as exact nanoseconds and are only formatted for display.
+pub(crate) mod child_commands {
+    use super::Error;
+    use crate::template::data;
+    use crate::template::runner::{
+        format_bytes, format_duration, print_bench_stats, print_result, BenchStats, Record,
+    };
     use crate::Day;
     use std::{
         io::{BufRead, BufReader},
-        path::Path,
         process::{Command, Stdio},
         thread,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// Prints a record like the binary prints it in text mode.
+    fn print_record(record: &Record) {
+        match record {
+            Record::Part {
+                part,
+                answer,
+                timings,
+                ..
+            } => {
+                let stats = BenchStats::from(*timings);
+                print_result(
+                    answer,
+                    &format!("Part {part}"),
+                    &format_duration(&stats.mean, stats.samples),
+                );
+                print_bench_stats(&stats);
+            }
+            Record::Parse { timings, .. } => {
+                let stats = BenchStats::from(*timings);
+                println!("Parse:{}", format_duration(&stats.mean, stats.samples));
+                print_bench_stats(&stats);
+            }
+            Record::Memory { peak_memory_bytes } => {
+                println!("Peak memory: {}", format_bytes(*peak_memory_bytes));
+            }
+        }
+    }
+
+    /// Run the solution bin for a given day, echoing its output as text if `echo` is set.
+    pub fn run_solution(
+        day: Day,
+        is_timed: bool,
+        is_release: bool,
+        echo: bool,
+    ) -> Result<Vec<String>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
+        if !data::bin_path(day).exists() {
             return Ok(vec![]);
         }
 
@@ -87,9 +124,11 @@ mod child_commands {
             args.push("--release");
         }
 
+        // the timings are read from the JSON output, whatever the format in `aoc.toml`.
+        args.extend(["--", "--format", "json"]);
+
         if is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
             args.push("--time");
         }
 
@@ -115,7 +154,12 @@ mod child_commands {
 
         for line in stdout.lines() {
             let line = line.unwrap();
-            println!("{line}");
+            if echo {
+                match Record::parse(&line) {
+                    Some(record) => print_record(&record),
+                    None => println!("{line}"),
+                }
+            }
             output.push(line);
         }
 
@@ -125,128 +169,155 @@ mod child_commands {
         Ok(output)
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timings {
-        let mut timings = super::Timings {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
-        };
+    /// Answers of both parts, `None` for unsolved parts.
+    pub fn parse_answers(output: &[String]) -> [Option<String>; 2] {
+        let mut answers = [None, None];
+
+        for record in output.iter().filter_map(|line| Record::parse(line)) {
+            if let Record::Part {
+                part: part @ 1..=2,
+                answer,
+                ..
+            } = record
+            {
+                answers[usize::from(part - 1)] = answer;
+            }
+        }
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
+        answers
+    }
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timings {
+        let mut timings = super::Timings::new(day);
+
+        for record in output.iter().filter_map(|line| Record::parse(line)) {
+            match record {
+                // unsolved parts are not benchmarked.
+                Record::Part { answer: None, .. } => {}
+                Record::Part {
+                    part, timings: run, ..
+                } => {
+                    let stats = Some(run.into());
+                    match part {
+                        1 => timings.part_1 = stats,
+                        2 => timings.part_2 = stats,
+                        _ => eprintln!("Unknown part in output: {part}"),
+                    }
                 }
-
-                timings.total_nanos += nanos;
-            });
+                Record::Parse { timings: parse, .. } => timings.parse = Some(parse.into()),
+                Record::Memory { peak_memory_bytes } => timings.memory = Some(peak_memory_bytes),
+            }
+        }
 
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
-    }
-
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
-    }
-
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
-
+        use super::{parse_answers, parse_exec_time};
         use crate::day;
+        use crate::template::memo::MemoStats;
+        use crate::template::runner::{
+            format_json, format_memory_json, format_parse_json, BenchStats,
+        };
+        use std::time::Duration;
+
+        fn stats(nanos: u64, samples: u128) -> BenchStats {
+            BenchStats {
+                mean: Duration::from_nanos(nanos),
+                median: Duration::from_nanos(nanos - 1),
+                p95: Duration::from_nanos(nanos + 1),
+                samples,
+            }
+        }
 
         #[test]
         fn test_well_formed() {
             let res = parse_exec_time(
                 &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
+                    r#"{"part":1,"answer":"0","nanos":74,"samples":100000,"median_nanos":70,"p95_nanos":90,"memo_hits":0,"memo_misses":0}"#.into(),
+                    r#"{"part":2,"answer":"10","nanos":74130000,"samples":99999,"median_nanos":74000000,"p95_nanos":80000000,"memo_hits":0,"memo_misses":0}"#.into(),
                     "".into(),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_eq!(res.total(), Duration::from_nanos(74130074));
+            assert_eq!(res.parse.is_none(), true);
+            assert_eq!(res.part_1.unwrap().mean, Duration::from_nanos(74));
+            assert_eq!(res.part_1.unwrap().median, Duration::from_nanos(70));
+            assert_eq!(res.part_2.unwrap().p95, Duration::from_millis(80));
+            assert_eq!(res.part_2.unwrap().samples, 99999);
+        }
+
+        #[test]
+        fn test_round_trip() {
+            let part_1 = stats(74, 100000);
+            let part_2 = stats(1_500_000, 10);
+            let parse = stats(1_500, 1000);
+
+            let res = parse_exec_time(
+                &[
+                    format_parse_json(&parse),
+                    format_json(1, Some(&0), &part_1, &MemoStats::default()),
+                    format_json(2, Some(&"a\"b"), &part_2, &MemoStats::default()),
+                    format_memory_json(4096 * 1024),
+                ],
+                day!(1),
+            );
+
+            assert_eq!(res.parse, Some(parse));
+            assert_eq!(res.part_1, Some(part_1));
+            assert_eq!(res.part_2, Some(part_2));
+            assert_eq!(res.memory, Some(4096 * 1024));
         }
 
         #[test]
         fn test_patterns_in_input() {
             let res = parse_exec_time(
                 &[
+                    "{ printed by the solution }".into(),
                     "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
+                    format_json(
+                        1,
+                        Some(&"} (1ms @ 5 samples)"),
+                        &stats(2_000_000_000, 5),
+                        &MemoStats::default(),
+                    ),
+                    format_json(
+                        2,
+                        Some(&"10s"),
+                        &stats(100_000_000, 1),
+                        &MemoStats::default(),
+                    ),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_eq!(res.total(), Duration::from_millis(2100));
+            assert_eq!(res.part_1.unwrap().mean, Duration::from_secs(2));
+            assert_eq!(res.part_2.unwrap().mean, Duration::from_millis(100));
+        }
+
+        #[test]
+        fn test_answers() {
+            let res = parse_answers(&[
+                "{ printed by the solution }".into(),
+                format_json(1, Some(&"a\nb"), &stats(10, 1), &MemoStats::default()),
+                format_json::<u32>(2, None, &stats(10, 1), &MemoStats::default()),
+            ]);
+            assert_eq!(res, [Some("a\nb".into()), None]);
         }
 
         #[test]
         fn test_missing_parts() {
             let res = parse_exec_time(
                 &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
+                    format_json::<u32>(1, None, &stats(10, 1), &MemoStats::default()),
+                    format_json::<u32>(2, None, &stats(10, 1), &MemoStats::default()),
                     "".into(),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 0_f64);
+            assert_eq!(res.total(), Duration::ZERO);
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
         }
@@ -1,5 +1,9 @@
 pub mod all;
 pub mod download;
 pub mod read;
+pub mod readme;
 pub mod scaffold;
+pub mod season;
 pub mod solve;
+pub mod stars;
+pub mod stats;
@@ -0,0 +1,40 @@
+use std::process;
+
+use crate::template::{
+    config, data,
+    readme::{Error, Readme},
+    readme_languages, readme_notes,
+};
+
+/// Updates the sections of the readme that need no run of the solutions: the notes of each day and the language
+/// statistics. Sections without markers in the readme are skipped.
+pub fn handle(dry_run: bool) {
+    match update(dry_run) {
+        Ok(false) => println!("README is up to date."),
+        Ok(true) if dry_run => {}
+        Ok(true) => println!("Successfully updated README."),
+        Err(e) => {
+            eprintln!("Failed to update readme: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+fn update(dry_run: bool) -> Result<bool, Error> {
+    let config = &config::get().readme;
+    let mut readme = Readme::load(&data::project_path(&config.path))?;
+
+    for day in readme_notes::update(&mut readme)? {
+        eprintln!(
+            "The notes of day {day} are not in the README, add a line `{}` before and after where they go.",
+            readme_notes::marker(day)
+        );
+    }
+
+    let marker = &config.language_stats_marker;
+    if readme.content().contains(marker.as_str()) {
+        readme_languages::update_content(readme.content_mut(), &readme_languages::load(), marker)?;
+    }
+
+    readme.save(dry_run)
+}
@@ -1,9 +1,11 @@
 use std::{
+    fmt::Display,
     fs::{File, OpenOptions},
-    io::Write,
+    io::{self, Write},
     process,
 };
 
+use crate::template::{aoc_cli, data, examples};
 use crate::Day;
 
 const MODULE_TEMPLATE: &str = r#"advent_of_code::solution!(DAY_NUMBER);
@@ -15,100 +17,123 @@ pub fn part_one(input: &str) -> Option<u32> {
 pub fn part_two(input: &str) -> Option<u32> {
     None
 }
+"#;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use tracing::{warn, Level};
-    use tracing_subscriber::FmtSubscriber;
-
-    #[test]
-    fn test_part_one() {
-        let subscriber = FmtSubscriber::builder()
-            .with_max_level(Level::TRACE)
-            .pretty()
-            .finish();
-
-        if let Err(e) = tracing::subscriber::set_global_default(subscriber) {
-            warn!("setting default subscriber failed: {:?}", e)
-        }
-
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
-    }
-
-    #[test]
-    fn test_part_two() {
-        let subscriber = FmtSubscriber::builder()
-            .with_max_level(Level::TRACE)
-            .pretty()
-            .finish();
-
-        if let Err(e) = tracing::subscriber::set_global_default(subscriber) {
-            warn!("setting default subscriber failed: {:?}", e)
-        }
+const MANIFEST_TEMPLATE: &str = r#"# Examples of day DAY_PADDED, checked by `cargo test --bin DAY_PADDED test_examples`.
+# Add the answer of each part given in the puzzle description, and `params = { name = value }` if the example needs
+# other constants than the real input.
 
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
-    }
-}
+# [[example]]
+# file = "DAY_PADDED.txt"
+# part = 1
+# answer = 0
 "#;
 
 fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
     OpenOptions::new().write(true).create_new(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+/// Creates an empty file, `false` if it already exists, e.g. an input downloaded before scaffolding.
+/// Existing files are left untouched, re-running the scaffold must keep inputs and examples.
+fn create_file(path: &str) -> Result<bool, std::io::Error> {
+    match safe_create_file(path) {
+        Ok(_) => Ok(true),
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => Ok(false),
+        Err(e) => Err(e),
+    }
 }
 
-pub fn handle(day: Day) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
-
-    let mut file = match safe_create_file(&module_path) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
+#[derive(Debug)]
+pub enum ScaffoldError {
+    ModuleFile(io::Error),
+    ModuleContents(io::Error),
+    InputFile(io::Error),
+    ExampleFile(io::Error),
+    ManifestFile(io::Error),
+}
+
+impl Display for ScaffoldError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScaffoldError::ModuleFile(e) => write!(f, "Failed to create module file: {e}"),
+            ScaffoldError::ModuleContents(e) => write!(f, "Failed to write module contents: {e}"),
+            ScaffoldError::InputFile(e) => write!(f, "Failed to create input file: {e}"),
+            ScaffoldError::ExampleFile(e) => write!(f, "Failed to create example file: {e}"),
+            ScaffoldError::ManifestFile(e) => write!(f, "Failed to create example manifest: {e}"),
         }
-    };
+    }
+}
+
+/// Creates the module, input and example files and the example manifest for a day.
+/// Fails if the module file already exists, existing input and example files are left untouched.
+pub fn create_files(day: Day) -> Result<(), ScaffoldError> {
+    let input_path = aoc_cli::get_input_path(day);
+    let example_path = aoc_cli::get_example_path(day);
+    let module_path = data::bin_path(day);
+
+    let mut file =
+        safe_create_file(&module_path.to_string_lossy()).map_err(ScaffoldError::ModuleFile)?;
 
-    match file.write_all(
+    file.write_all(
         MODULE_TEMPLATE
             .replace("DAY_NUMBER", &day.into_inner().to_string())
             .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
-        }
+    )
+    .map_err(ScaffoldError::ModuleContents)?;
+    println!("Created module file \"{}\"", module_path.display());
+
+    if create_file(&input_path).map_err(ScaffoldError::InputFile)? {
+        println!("Created empty input file \"{}\"", &input_path);
     }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
-        }
+    if create_file(&example_path).map_err(ScaffoldError::ExampleFile)? {
+        println!("Created empty example file \"{}\"", &example_path);
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
-        }
+    let manifest_path = examples::manifest_path(day);
+    // an existing manifest is kept, like the example files it lists.
+    if !manifest_path.exists() {
+        safe_create_file(&manifest_path.to_string_lossy())
+            .and_then(|mut file| {
+                file.write_all(
+                    MANIFEST_TEMPLATE
+                        .replace("DAY_PADDED", &day.to_string())
+                        .as_bytes(),
+                )
+            })
+            .map_err(ScaffoldError::ManifestFile)?;
+        println!("Created example manifest \"{}\"", manifest_path.display());
+    }
+
+    Ok(())
+}
+
+pub fn handle(day: Day) {
+    if let Err(e) = create_files(day) {
+        eprintln!("{e}");
+        process::exit(1);
     }
 
     println!("---");
     println!("🎄 Type `cargo solve {}` to run your solution.", day);
 }
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::create_file;
+    use std::fs;
+
+    #[test]
+    fn keeps_existing_files() {
+        let dir = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("01.txt").to_string_lossy().into_owned();
+
+        assert!(create_file(&path).unwrap());
+        fs::write(&path, "downloaded input").unwrap();
+        assert!(!create_file(&path).unwrap());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "downloaded input");
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,247 @@
+use std::{
+    fs, process,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::template::{
+    aoc_cli, commands::scaffold, config, data, readme_stars::Progress, ANSI_BOLD, ANSI_RESET,
+};
+use crate::{all_days, Day};
+
+/// Sets up every unlocked day that has not been scaffolded yet and prints the state of the season.
+/// Days with an existing `src/bin/NN.rs` are never touched, so the command can be re-run at any time.
+pub fn handle() {
+    let Some(year) = aoc_cli::get_year() else {
+        eprintln!(
+            "The year is not set. Set `year` in \"aoc.toml\" or pass `--year` with the year you are solving."
+        );
+        process::exit(1);
+    };
+
+    let has_aoc_cli = aoc_cli::check().is_ok();
+    if !has_aoc_cli {
+        eprintln!("command \"aoc\" not found or not callable, inputs and puzzles will not be downloaded. Try running \"cargo install aoc-cli\" to install it.");
+    }
+
+    let now = SystemTime::now();
+    let unlocked: Vec<Day> = all_days()
+        .filter(|day| is_unlocked(year, *day, now))
+        .collect();
+
+    for &day in &unlocked {
+        if data::bin_path(day).exists() {
+            continue;
+        }
+
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+
+        if has_aoc_cli {
+            if let Err(e) = aoc_cli::download(day) {
+                eprintln!("failed to call aoc-cli: {e}");
+            }
+        }
+
+        if let Err(e) = scaffold::create_files(day) {
+            eprintln!("{e}");
+            continue;
+        }
+
+        println!();
+    }
+
+    for &day in &unlocked {
+        if let Some(path) = write_example(day) {
+            println!("Extracted example to \"{path}\"");
+        }
+    }
+
+    println!();
+    print_status(&unlocked);
+}
+
+/// Days since the unix epoch for a date of the gregorian calendar.
+/// see: https://howardhinnant.github.io/date_algorithms.html#days_from_civil
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Puzzles unlock at midnight EST (UTC-5) on their day of December.
+fn unlock_time(year: u16, day: Day) -> SystemTime {
+    let days = days_from_civil(i64::from(year), 12, i64::from(day.into_inner()));
+    let secs = u64::try_from(days * 86_400 + 5 * 3_600).unwrap_or(0);
+    UNIX_EPOCH + Duration::from_secs(secs)
+}
+
+#[must_use]
+pub fn is_unlocked(year: u16, day: Day, now: SystemTime) -> bool {
+    now >= unlock_time(year, day)
+}
+
+/// Finds the example input in a puzzle description: the first fenced code block spanning several lines,
+/// or the first code block if all of them are single lines.
+#[must_use]
+pub fn extract_example(puzzle: &str) -> Option<String> {
+    let mut blocks: Vec<Vec<&str>> = vec![];
+    let mut current: Option<Vec<&str>> = None;
+
+    for line in puzzle.lines() {
+        if line.trim_start().starts_with("```") {
+            match current.take() {
+                Some(block) => blocks.push(block),
+                None => current = Some(vec![]),
+            }
+        } else if let Some(block) = current.as_mut() {
+            block.push(line);
+        }
+    }
+
+    blocks
+        .iter()
+        .find(|block| block.len() > 1)
+        .or_else(|| blocks.first())
+        .map(|block| block.join("\n"))
+}
+
+/// Writes the example of a downloaded puzzle to the example file, unless that file already has content.
+fn write_example(day: Day) -> Option<String> {
+    let example_path = aoc_cli::get_example_path(day);
+
+    let is_empty = fs::read_to_string(&example_path).map_or(true, |s| s.trim().is_empty());
+    if !is_empty {
+        return None;
+    }
+
+    let puzzle = fs::read_to_string(aoc_cli::get_puzzle_path(day)).ok()?;
+    let example = extract_example(&puzzle)?;
+    fs::write(&example_path, example).ok()?;
+
+    Some(example_path)
+}
+
+/// Number of accepted answers, as recorded by aoc-cli in the downloaded puzzle description.
+#[must_use]
+pub fn count_stars(puzzle: &str) -> usize {
+    puzzle.matches("Your puzzle answer was").count()
+}
+
+/// Parts of a day accepted by Advent of Code, as recorded in the progress file of `cargo stars` or in the
+/// downloaded puzzle description. Parts are accepted in order, so `n` stars of the description are the first `n`.
+#[must_use]
+pub fn accepted_parts(progress: &Progress, day: Day, puzzle: Option<&str>) -> [bool; 2] {
+    let stars = puzzle.map_or(0, count_stars);
+    [1, 2].map(|part| progress.is_accepted(day, part) || stars >= usize::from(part))
+}
+
+fn print_status(unlocked: &[Day]) {
+    let progress = Progress::load(&data::project_path(&config::get().readme.progress))
+        .unwrap_or_else(|e| {
+            eprintln!("Failed to read the progress file: {e}");
+            Progress::default()
+        });
+
+    println!("{ANSI_BOLD}| Day | Scaffolded | Part 1 | Part 2 | Stars |{ANSI_RESET}");
+
+    for day in all_days() {
+        if !unlocked.contains(&day) {
+            println!("| {day}  | locked     |        |        |       |");
+            continue;
+        }
+
+        let puzzle = fs::read_to_string(aoc_cli::get_puzzle_path(day)).ok();
+        let parts = accepted_parts(&progress, day, puzzle.as_deref());
+
+        let mark = |done: bool| if done { "✔" } else { "✖" };
+
+        println!(
+            "| {day}  | {:<10} | {:<6} | {:<6} | {:<5} |",
+            mark(data::bin_path(day).exists()),
+            mark(parts[0]),
+            mark(parts[1]),
+            "⭐".repeat(parts.iter().filter(|&&part| part).count())
+        );
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{accepted_parts, count_stars, days_from_civil, extract_example, is_unlocked};
+    use crate::day;
+    use crate::template::readme_stars::Progress;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn converts_dates() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2000, 3, 1), 11_017);
+        assert_eq!(days_from_civil(2023, 12, 1), 19_692);
+    }
+
+    #[test]
+    fn unlocks_at_midnight_est() {
+        // 2023-12-01T05:00:00Z
+        let unlock = UNIX_EPOCH + Duration::from_secs(1_701_406_800);
+
+        assert!(is_unlocked(2023, day!(1), unlock));
+        assert!(!is_unlocked(2023, day!(1), unlock - Duration::from_secs(1)));
+        assert!(!is_unlocked(2023, day!(2), unlock));
+        assert!(is_unlocked(2022, day!(25), unlock));
+    }
+
+    #[test]
+    fn extracts_first_multiline_block() {
+        let puzzle = [
+            "Some text with `inline` code.",
+            "```",
+            "42",
+            "```",
+            "For example:",
+            "```",
+            "1abc2",
+            "pqr3stu8vwx",
+            "```",
+            "```",
+            "a2b",
+            "c4d",
+            "```",
+        ]
+        .join("\n");
+
+        assert_eq!(extract_example(&puzzle), Some("1abc2\npqr3stu8vwx".into()));
+        assert_eq!(extract_example("no code"), None);
+    }
+
+    #[test]
+    fn reads_accepted_parts() {
+        let mut progress = Progress::default();
+        progress.accept(day!(2), 2);
+        let one_star = "Your puzzle answer was `1`.";
+
+        assert_eq!(accepted_parts(&progress, day!(1), None), [false, false]);
+        assert_eq!(
+            accepted_parts(&progress, day!(1), Some(one_star)),
+            [true, false]
+        );
+        assert_eq!(
+            accepted_parts(&progress, day!(2), Some(one_star)),
+            [true, true]
+        );
+        assert_eq!(
+            accepted_parts(&progress, day!(3), Some("--- Day 3 ---")),
+            [false, false]
+        );
+    }
+
+    #[test]
+    fn counts_stars() {
+        assert_eq!(
+            count_stars("Your puzzle answer was `1`.\nYour puzzle answer was `2`."),
+            2
+        );
+        assert_eq!(count_stars("--- Day 1 ---"), 0);
+    }
+}
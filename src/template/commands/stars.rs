@@ -0,0 +1,32 @@
+use std::process;
+
+use crate::template::{config, data, readme_stars, readme_stars::Progress};
+use crate::Day;
+
+pub fn handle(accepted: Option<(Day, u8)>, dry_run: bool) {
+    let path = &data::project_path(&config::get().readme.progress);
+
+    let progress = Progress::load(path).and_then(|mut progress| {
+        if let Some((day, part)) = accepted {
+            progress.accept(day, part);
+            if !dry_run {
+                progress.save(path)?;
+            }
+        }
+        Ok(progress)
+    });
+
+    let progress = progress.unwrap_or_else(|e| {
+        eprintln!("Failed to update the progress file: {e}");
+        process::exit(1);
+    });
+
+    match readme_stars::update(&progress, dry_run) {
+        Ok(()) if dry_run => {}
+        Ok(()) => println!("Successfully updated README with stars."),
+        Err(e) => {
+            eprintln!("Failed to update readme with stars: {e}");
+            process::exit(1);
+        }
+    }
+}
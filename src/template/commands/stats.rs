@@ -0,0 +1,53 @@
+use std::{fs, time::Duration};
+
+use crate::template::{
+    commands::all::child_commands,
+    config, data,
+    readme_benchmarks::count_lines_of_code,
+    stats::{find_crates, DayStats, Report},
+};
+use crate::{all_days, Day};
+
+pub fn handle(is_release: bool, is_timed: bool, markdown: bool, budget_ms: Option<u64>) {
+    let budget = Duration::from_millis(budget_ms.unwrap_or(config::get().stats.budget_ms));
+
+    let days: Vec<DayStats> = all_days()
+        .filter_map(|day| collect(day, is_timed, is_release))
+        .collect();
+
+    if days.is_empty() {
+        println!("No solutions yet.");
+        return;
+    }
+
+    let report = Report::new(days, budget);
+
+    if markdown {
+        print!("{}", report.to_markdown());
+    } else {
+        print!("{}", report.to_terminal());
+    }
+}
+
+/// Runs the solution of a day quietly, `None` for days that have not been scaffolded yet.
+fn collect(day: Day, is_timed: bool, is_release: bool) -> Option<DayStats> {
+    let source = fs::read_to_string(data::bin_path(day)).ok()?;
+
+    // progress goes to stderr, keeping stdout for the report.
+    eprintln!("Running day {day}...");
+
+    let output =
+        child_commands::run_solution(day, is_timed, is_release, false).unwrap_or_else(|e| {
+            eprintln!("Failed to run day {day}: {e:?}");
+            vec![]
+        });
+
+    let mut timings = child_commands::parse_exec_time(&output, day);
+    timings.lines_of_code = Some(count_lines_of_code(&source));
+
+    Some(DayStats {
+        timings,
+        answers: child_commands::parse_answers(&output),
+        crates: find_crates(&source),
+    })
+}
@@ -0,0 +1,291 @@
+/// Module that loads the project configuration from `aoc.toml`.
+/// Every setting is optional, a missing file or section falls back to the template defaults.
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    process,
+    sync::OnceLock,
+};
+
+use serde::Deserialize;
+
+use crate::template::{
+    data, readme_benchmarks, readme_languages, readme_stars, runner::OutputFormat,
+};
+
+/// Default location of the configuration file, relative to the project root.
+pub const CONFIG_FILE: &str = "aoc.toml";
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Year of the puzzles. `--year` and `AOC_YEAR` take precedence.
+    pub year: Option<u16>,
+    pub data: DataConfig,
+    pub bench: BenchConfig,
+    pub readme: ReadmeConfig,
+    pub output: OutputConfig,
+    pub submit: SubmitConfig,
+    pub stats: StatsConfig,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DataConfig {
+    /// Directory holding one sub-directory per kind of data file.
+    pub root: PathBuf,
+    /// Overrides `<root>/inputs`.
+    pub inputs: Option<PathBuf>,
+    /// Overrides `<root>/examples`.
+    pub examples: Option<PathBuf>,
+    /// Overrides `<root>/puzzles`.
+    pub puzzles: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BenchConfig {
+    /// Time spent benchmarking each part with `--time`, in milliseconds.
+    pub budget_ms: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StatsConfig {
+    /// Days taking longer than this in total are listed in the hall of shame of `stats`, in milliseconds.
+    pub budget_ms: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ReadmeConfig {
+    pub path: PathBuf,
+    /// Marker surrounding the benchmark table.
+    pub benchmarks_marker: String,
+    /// Marker surrounding the stars table.
+    pub stars_marker: String,
+    /// File recording the accepted parts, the source of the stars table.
+    pub progress: PathBuf,
+    /// Marker surrounding the language statistics.
+    pub language_stats_marker: String,
+    /// Directory of the notes of each day, e.g. `05.md`.
+    pub notes: PathBuf,
+    pub benchmarks: readme_benchmarks::TableConfig,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct OutputConfig {
+    pub format: OutputFormat,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SubmitConfig {
+    pub backend: SubmitBackend,
+}
+
+/// How `--submit` hands answers to Advent of Code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SubmitBackend {
+    /// Submit through the `aoc` command of aoc-cli.
+    #[default]
+    AocCli,
+    /// Print the answer and the puzzle URL to submit it by hand.
+    Manual,
+}
+
+impl Default for DataConfig {
+    fn default() -> Self {
+        DataConfig {
+            root: PathBuf::from("data"),
+            inputs: None,
+            examples: None,
+            puzzles: None,
+        }
+    }
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        BenchConfig { budget_ms: 1000 }
+    }
+}
+
+impl Default for StatsConfig {
+    fn default() -> Self {
+        StatsConfig { budget_ms: 1000 }
+    }
+}
+
+impl Default for ReadmeConfig {
+    fn default() -> Self {
+        ReadmeConfig {
+            path: PathBuf::from("README.md"),
+            benchmarks_marker: readme_benchmarks::MARKER.into(),
+            stars_marker: readme_stars::MARKER.into(),
+            progress: PathBuf::from("data/progress.toml"),
+            language_stats_marker: readme_languages::MARKER.into(),
+            notes: PathBuf::from("data/notes"),
+            benchmarks: readme_benchmarks::TableConfig::default(),
+        }
+    }
+}
+
+impl DataConfig {
+    /// Directory of a kind of data file, e.g. `inputs` or `examples`.
+    #[must_use]
+    pub fn dir(&self, folder: &str) -> PathBuf {
+        let configured = match folder {
+            "inputs" => self.inputs.as_ref(),
+            "examples" => self.examples.as_ref(),
+            "puzzles" => self.puzzles.as_ref(),
+            _ => None,
+        };
+
+        configured
+            .cloned()
+            .unwrap_or_else(|| self.root.join(folder))
+    }
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Read(PathBuf, io::Error),
+    Parse(PathBuf, toml::de::Error),
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::Read(path, e) => write!(f, "Failed to read \"{}\": {e}", path.display()),
+            ConfigError::Parse(path, e) => {
+                write!(f, "Failed to parse \"{}\": {e}", path.display())
+            }
+        }
+    }
+}
+
+impl Config {
+    /// Parses a configuration from the contents of an `aoc.toml` file.
+    pub fn parse(s: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(s)
+    }
+
+    /// Loads the configuration at `path`. A missing file yields the defaults.
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        match fs::read_to_string(path) {
+            Ok(s) => Config::parse(&s).map_err(|e| ConfigError::Parse(path.into(), e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Config::default()),
+            Err(e) => Err(ConfigError::Read(path.into(), e)),
+        }
+    }
+}
+
+/// Path of the configuration file: `AOC_CONFIG` if set (see `--config`), `aoc.toml` of the project root otherwise.
+#[must_use]
+pub fn config_path() -> PathBuf {
+    env::var_os("AOC_CONFIG").map_or_else(
+        || {
+            let root = env::current_dir()
+                .ok()
+                .and_then(|cwd| data::find_root(&cwd))
+                .unwrap_or_default();
+            root.join(CONFIG_FILE)
+        },
+        PathBuf::from,
+    )
+}
+
+/// The configuration of the project, loaded on first use. Exits the process if the file is invalid.
+pub fn get() -> &'static Config {
+    static CONFIG: OnceLock<Config> = OnceLock::new();
+
+    CONFIG.get_or_init(|| {
+        Config::load(&config_path()).unwrap_or_else(|e| {
+            eprintln!("{e}");
+            process::exit(1);
+        })
+    })
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Config, SubmitBackend};
+    use crate::template::readme_benchmarks::{Column, SortOrder};
+    use crate::template::runner::OutputFormat;
+    use std::path::PathBuf;
+
+    #[test]
+    fn parses_empty_config() {
+        assert_eq!(Config::parse("").unwrap(), Config::default());
+    }
+
+    #[test]
+    fn parses_full_config() {
+        let config = Config::parse(
+            r#"
+            year = 2022
+
+            [data]
+            root = "puzzles"
+            inputs = "/secret/inputs"
+
+            [bench]
+            budget_ms = 250
+
+            [readme]
+            path = "docs/README.md"
+            benchmarks_marker = "<!-- bench -->"
+            progress = "stars.toml"
+            notes = "docs/notes"
+
+            [readme.benchmarks]
+            columns = ["part_1", "part_2", "p95", "delta"]
+            sort = "cost"
+            chart = "bench.svg"
+
+            [output]
+            format = "json"
+
+            [submit]
+            backend = "manual"
+
+            [stats]
+            budget_ms = 50
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.year, Some(2022));
+        assert_eq!(config.data.dir("inputs"), PathBuf::from("/secret/inputs"));
+        assert_eq!(
+            config.data.dir("examples"),
+            PathBuf::from("puzzles/examples")
+        );
+        assert_eq!(config.bench.budget_ms, 250);
+        assert_eq!(config.readme.path, PathBuf::from("docs/README.md"));
+        assert_eq!(config.readme.benchmarks_marker, "<!-- bench -->");
+        assert_eq!(config.readme.progress, PathBuf::from("stars.toml"));
+        assert_eq!(config.readme.notes, PathBuf::from("docs/notes"));
+        assert_eq!(
+            config.readme.benchmarks.columns,
+            [Column::Part1, Column::Part2, Column::P95, Column::Delta]
+        );
+        assert_eq!(config.readme.benchmarks.sort, SortOrder::Cost);
+        assert_eq!(config.readme.benchmarks.chart.as_deref(), Some("bench.svg"));
+        assert_eq!(config.output.format, OutputFormat::Json);
+        assert_eq!(config.submit.backend, SubmitBackend::Manual);
+        assert_eq!(config.stats.budget_ms, 50);
+    }
+
+    #[test]
+    fn rejects_unknown_keys() {
+        assert!(Config::parse("yaer = 2023").is_err());
+        assert!(Config::parse("[submit]\nbackend = \"email\"").is_err());
+        assert!(Config::parse("[readme.benchmarks]\ncolumns = [\"speed\"]").is_err());
+    }
+}
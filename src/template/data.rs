@@ -0,0 +1,249 @@
+/// Module that reads the data files of the project, e.g. `data/inputs/01.txt`, from any of its directories.
+/// The data directories of `aoc.toml` are relative to the project root, the closest directory holding a `Cargo.toml`.
+/// Files are read with `\n` line endings and without trailing newlines, whatever the editor or download left in them.
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::template::config::{self, DataConfig};
+use crate::Day;
+
+/// File marking the project root.
+const ROOT_MARKER: &str = "Cargo.toml";
+
+#[derive(Debug)]
+pub enum DataError {
+    /// No directory from the given one up holds a `Cargo.toml`.
+    NoProjectRoot(PathBuf),
+    /// The input of a day was not downloaded yet.
+    MissingInput(Day, PathBuf),
+    Missing(PathBuf),
+    Read(PathBuf, io::Error),
+}
+
+impl Display for DataError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DataError::NoProjectRoot(dir) => write!(
+                f,
+                "Could not find the project root: no {ROOT_MARKER} in \"{}\" or its parents.",
+                dir.display()
+            ),
+            DataError::MissingInput(day, path) => write!(
+                f,
+                "Input file \"{}\" does not exist. Run `cargo download {day}` to download it.",
+                path.display()
+            ),
+            DataError::Missing(path) => write!(f, "File \"{}\" does not exist.", path.display()),
+            DataError::Read(path, e) => write!(f, "Failed to read \"{}\": {e}", path.display()),
+        }
+    }
+}
+
+/// The data directories of a project.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataStore {
+    root: PathBuf,
+    config: DataConfig,
+}
+
+impl DataStore {
+    #[must_use]
+    pub fn new(root: impl Into<PathBuf>, config: DataConfig) -> Self {
+        DataStore {
+            root: root.into(),
+            config,
+        }
+    }
+
+    /// The data directories of the project around the working directory, as configured in `aoc.toml`.
+    pub fn locate() -> Result<Self, DataError> {
+        let cwd = env::current_dir().map_err(|e| DataError::Read(".".into(), e))?;
+        let root = find_root(&cwd).ok_or(DataError::NoProjectRoot(cwd))?;
+        Ok(DataStore::new(root, config::get().data.clone()))
+    }
+
+    #[must_use]
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Directory of a kind of data file, e.g. `inputs`. Absolute directories of the configuration are kept as is.
+    #[must_use]
+    pub fn dir(&self, folder: &str) -> PathBuf {
+        self.root.join(self.config.dir(folder))
+    }
+
+    #[must_use]
+    pub fn path(&self, folder: &str, file: &str) -> PathBuf {
+        self.dir(folder).join(file)
+    }
+
+    /// Reads a data file, e.g. `read("examples", "08-2.txt")`.
+    pub fn read(&self, folder: &str, file: &str) -> Result<String, DataError> {
+        read(&self.path(folder, file))
+    }
+
+    /// Reads the file of a day, e.g. `01.txt`.
+    pub fn read_day(&self, folder: &str, day: Day) -> Result<String, DataError> {
+        self.read(folder, &format!("{day}.txt"))
+    }
+
+    /// Reads the file of a part of a day, e.g. `01-2.txt`.
+    pub fn read_day_part(&self, folder: &str, day: Day, part: u8) -> Result<String, DataError> {
+        self.read(folder, &format!("{day}-{part}.txt"))
+    }
+
+    /// Reads the puzzle input of a day, pointing to `cargo download` if it is missing.
+    pub fn input(&self, day: Day) -> Result<String, DataError> {
+        self.read_day("inputs", day).map_err(|e| match e {
+            DataError::Missing(path) => DataError::MissingInput(day, path),
+            e => e,
+        })
+    }
+
+    pub fn example(&self, day: Day) -> Result<String, DataError> {
+        self.read_day("examples", day)
+    }
+}
+
+/// The closest directory holding a `Cargo.toml`, starting at `start`.
+#[must_use]
+pub fn find_root(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .find(|dir| dir.join(ROOT_MARKER).is_file())
+        .map(Path::to_path_buf)
+}
+
+/// Path of a data file to create or pass on, e.g. to aoc-cli. Relative to the working directory when it is the
+/// project root, so that messages keep showing `data/inputs/01.txt`.
+#[must_use]
+pub fn data_path(folder: &str, file: &str) -> PathBuf {
+    project_path(&config::get().data.dir(folder).join(file))
+}
+
+/// Path of the solution of a day, e.g. `src/bin/01.rs`, resolved against the project root like [`data_path`].
+#[must_use]
+pub fn bin_path(day: Day) -> PathBuf {
+    project_path(Path::new(&format!("src/bin/{day}.rs")))
+}
+
+/// A path of `aoc.toml`, e.g. of the readme, resolved against the project root like the data directories.
+/// Absolute paths are kept as they are.
+#[must_use]
+pub fn project_path(path: &Path) -> PathBuf {
+    match env::current_dir() {
+        Ok(cwd) => resolve(&cwd, path),
+        Err(_) => path.to_path_buf(),
+    }
+}
+
+/// `path` relative to the project root around `cwd`, kept relative when `cwd` is the root.
+fn resolve(cwd: &Path, path: &Path) -> PathBuf {
+    match find_root(cwd) {
+        Some(root) if root != cwd => root.join(path),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// Reads any file with the line endings and trailing newlines of data files normalised.
+pub fn read(path: &Path) -> Result<String, DataError> {
+    match fs::read_to_string(path) {
+        Ok(s) => Ok(normalise(&s)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Err(DataError::Missing(path.into())),
+        Err(e) => Err(DataError::Read(path.into(), e)),
+    }
+}
+
+/// Replaces `\r\n` with `\n` and removes trailing newlines, so inputs look the same on every platform and examples
+/// pasted with or without a final newline parse the same.
+#[must_use]
+pub fn normalise(s: &str) -> String {
+    s.replace("\r\n", "\n").trim_end_matches('\n').to_string()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{find_root, normalise, resolve, DataError, DataStore};
+    use crate::template::config::DataConfig;
+    use crate::Day;
+    use std::{fs, path::PathBuf};
+
+    fn project(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-{name}-{}", std::process::id()));
+        fs::create_dir_all(dir.join("data/inputs")).unwrap();
+        fs::create_dir_all(dir.join("src/bin")).unwrap();
+        fs::write(dir.join("Cargo.toml"), "").unwrap();
+        dir
+    }
+
+    fn store(root: &PathBuf) -> DataStore {
+        DataStore::new(root, DataConfig::default())
+    }
+
+    #[test]
+    fn normalises_line_endings() {
+        assert_eq!(normalise("a\r\nb\r\n"), "a\nb");
+        assert_eq!(normalise("a\n\nb\n\n\n"), "a\n\nb");
+        assert_eq!(normalise("a\nb"), "a\nb");
+        assert_eq!(normalise("\n"), "");
+    }
+
+    #[test]
+    fn reads_from_project_root() {
+        let dir = project("data");
+        assert_eq!(find_root(&dir.join("src/bin")), Some(dir.clone()));
+        assert_eq!(find_root(&dir), Some(dir.clone()));
+
+        let store = store(&find_root(&dir.join("src/bin")).unwrap());
+        fs::write(dir.join("data/inputs/01.txt"), "1\r\n2\r\n").unwrap();
+        assert_eq!(store.input(Day::new(1).unwrap()).unwrap(), "1\n2");
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn resolves_paths_against_project_root() {
+        let dir = project("paths");
+        let readme = PathBuf::from("README.md");
+
+        assert_eq!(
+            resolve(&dir.join("src/bin"), &readme),
+            dir.join("README.md")
+        );
+        assert_eq!(resolve(&dir, &readme), readme);
+        assert_eq!(
+            resolve(&dir.join("src"), &PathBuf::from("/tmp/README.md")),
+            PathBuf::from("/tmp/README.md")
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn reports_missing_files_with_their_path() {
+        let store = store(&PathBuf::from("/project"));
+        let day = Day::new(3).unwrap();
+
+        let DataError::MissingInput(_, path) = store.input(day).unwrap_err() else {
+            panic!("expected a missing input");
+        };
+        assert_eq!(path, PathBuf::from("/project/data/inputs/03.txt"));
+        assert_eq!(
+            store.input(day).unwrap_err().to_string(),
+            "Input file \"/project/data/inputs/03.txt\" does not exist. Run `cargo download 03` to download it."
+        );
+
+        assert_eq!(
+            store
+                .read_day_part("examples", day, 2)
+                .unwrap_err()
+                .to_string(),
+            "File \"/project/data/examples/03-2.txt\" does not exist."
+        );
+    }
+}
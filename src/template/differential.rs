@@ -0,0 +1,306 @@
+/// Module that checks a part against a slow reference implementation of it.
+/// A brute force is easy to get right but too slow for real inputs. Both run on the examples of the day listed in its
+/// manifest (see [`examples`]) and on small generated inputs, and the first input on which they disagree is reported.
+use std::{
+    collections::BTreeMap,
+    fmt::{Debug, Display},
+};
+
+use crate::template::{
+    data::DataStore,
+    examples::{self, Example},
+    fuzz::{Generator, Rng, SEED},
+    params,
+};
+use crate::Day;
+
+/// Where a divergent input comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    /// An example: its file and parameters, or its position among the inputs of [`Differential::with_example`].
+    Example(String),
+    /// The n-th generated input, counting from 0.
+    Generated(usize),
+}
+
+/// An input on which a part and its reference disagree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Divergence {
+    pub part: u8,
+    pub source: Source,
+    pub input: String,
+    pub solution: String,
+    pub reference: String,
+}
+
+impl Display for Divergence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let source = match &self.source {
+            Source::Example(name) => format!("example {name}"),
+            Source::Generated(i) => format!("generated input {i}"),
+        };
+
+        write!(
+            f,
+            "Part {} diverges from its reference on {source}: {} instead of {}\n--- input ---\n{}\n-------------",
+            self.part, self.solution, self.reference, self.input
+        )
+    }
+}
+
+/// An example input, run with the parameters of its manifest entry.
+struct ExampleInput {
+    name: String,
+    input: String,
+    params: BTreeMap<String, String>,
+}
+
+/// A part and its reference implementation, both taking the raw input.
+pub struct Differential<A> {
+    part: u8,
+    solution: fn(&str) -> A,
+    reference: fn(&str) -> A,
+    day: Option<Day>,
+    keep_example: fn(&Example) -> bool,
+    examples: Vec<ExampleInput>,
+    cases: usize,
+    seed: u64,
+}
+
+impl<A: PartialEq + Debug> Differential<A> {
+    /// A part and its reference, checked on the inputs of [`Differential::with_example`] and generated ones.
+    #[must_use]
+    pub fn new(part: u8, solution: fn(&str) -> A, reference: fn(&str) -> A) -> Self {
+        Differential {
+            part,
+            solution,
+            reference,
+            day: None,
+            keep_example: |_| true,
+            examples: vec![],
+            cases: 100,
+            seed: SEED,
+        }
+    }
+
+    /// A part of a day and its reference, checked on the examples of the part in the manifest of the day first.
+    #[must_use]
+    pub fn for_day(day: Day, part: u8, solution: fn(&str) -> A, reference: fn(&str) -> A) -> Self {
+        Differential {
+            day: Some(day),
+            ..Differential::new(part, solution, reference)
+        }
+    }
+
+    /// Adds an input checked before the generated ones.
+    #[must_use]
+    pub fn with_example(mut self, input: impl Into<String>) -> Self {
+        self.examples.push(ExampleInput {
+            name: self.examples.len().to_string(),
+            input: input.into(),
+            params: BTreeMap::new(),
+        });
+        self
+    }
+
+    /// Only checks the examples of the manifest for which `keep` holds, e.g. to leave out an example too large for
+    /// the reference.
+    #[must_use]
+    pub fn filter_examples(mut self, keep: fn(&Example) -> bool) -> Self {
+        self.keep_example = keep;
+        self
+    }
+
+    /// Sets the number of generated inputs, 100 by default. Keep them small, the reference is slow.
+    #[must_use]
+    pub fn with_cases(mut self, cases: usize) -> Self {
+        self.cases = cases;
+        self
+    }
+
+    #[must_use]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Compares the part and its reference on the examples, then on inputs of `generator`.
+    ///
+    /// # Panics
+    ///
+    /// If the manifest of the day or one of its example files cannot be read.
+    pub fn run(&self, generator: &impl Generator) -> Result<(), Divergence> {
+        for example in self.examples.iter().chain(&self.manifest_examples()) {
+            params::with(&example.params, || {
+                self.compare(Source::Example(example.name.clone()), &example.input)
+            })?;
+        }
+
+        let mut rng = Rng::new(self.seed);
+
+        for case in 0..self.cases {
+            self.compare(Source::Generated(case), &generator.generate(&mut rng))?;
+        }
+
+        Ok(())
+    }
+
+    /// Like [`Differential::run`], panicking with the report of the first divergence.
+    pub fn check(&self, generator: &impl Generator) {
+        if let Err(divergence) = self.run(generator) {
+            panic!("{divergence}");
+        }
+    }
+
+    /// The examples of the part in the manifest of the day, named after their file and parameters.
+    fn manifest_examples(&self) -> Vec<ExampleInput> {
+        let Some(day) = self.day else {
+            return vec![];
+        };
+
+        let examples =
+            examples::load(day).unwrap_or_else(|e| panic!("Invalid example manifest {e}"));
+        let store = DataStore::locate().unwrap_or_else(|e| panic!("{e}"));
+
+        examples
+            .into_iter()
+            .filter(|example| example.part == self.part && (self.keep_example)(example))
+            .map(|example| {
+                let input = store
+                    .read("examples", &example.file)
+                    .unwrap_or_else(|e| panic!("Could not read example: {e}"));
+                let params: Vec<String> = example
+                    .params
+                    .iter()
+                    .map(|(name, value)| format!("{name}={value}"))
+                    .collect();
+                let name = if params.is_empty() {
+                    example.file
+                } else {
+                    format!("{} with {}", example.file, params.join(", "))
+                };
+
+                ExampleInput {
+                    name,
+                    input,
+                    params: example.params,
+                }
+            })
+            .collect()
+    }
+
+    fn compare(&self, source: Source, input: &str) -> Result<(), Divergence> {
+        let solution = (self.solution)(input);
+        let reference = (self.reference)(input);
+
+        if solution == reference {
+            return Ok(());
+        }
+
+        Err(Divergence {
+            part: self.part,
+            source,
+            input: input.to_string(),
+            solution: format!("{solution:?}"),
+            reference: format!("{reference:?}"),
+        })
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Differential, Source};
+    use crate::day;
+    use crate::template::fuzz::{Generator, Rng};
+
+    struct Words;
+
+    impl Generator for Words {
+        fn generate(&self, rng: &mut Rng) -> String {
+            let len = rng.range(0, 6) as usize;
+            rng.string("ab ", len)
+        }
+    }
+
+    fn count_words(input: &str) -> usize {
+        input.split_whitespace().count()
+    }
+
+    fn count_words_slowly(input: &str) -> usize {
+        input
+            .chars()
+            .zip(std::iter::once(' ').chain(input.chars()))
+            .filter(|&(c, before)| c != ' ' && before == ' ')
+            .count()
+    }
+
+    fn count_spaces(input: &str) -> usize {
+        input.matches(' ').count() + 1
+    }
+
+    #[test]
+    fn agrees_with_reference() {
+        assert!(Differential::new(1, count_words, count_words_slowly)
+            .with_example("a bb  a")
+            .run(&Words)
+            .is_ok());
+    }
+
+    #[test]
+    fn reports_examples_first() {
+        let divergence = Differential::new(2, count_spaces, count_words_slowly)
+            .with_example("a b")
+            .with_example("a  b")
+            .run(&Words)
+            .unwrap_err();
+
+        assert_eq!(divergence.part, 2);
+        assert_eq!(divergence.source, Source::Example("1".into()));
+        assert_eq!(divergence.solution, "3");
+        assert_eq!(divergence.reference, "2");
+    }
+
+    #[test]
+    fn reports_first_generated_divergence() {
+        let divergence = Differential::new(1, count_spaces, count_words_slowly)
+            .with_seed(7)
+            .run(&Words)
+            .unwrap_err();
+
+        let Source::Generated(case) = divergence.source else {
+            panic!("expected a generated input");
+        };
+
+        let mut rng = Rng::new(7);
+        let inputs: Vec<String> = (0..=case).map(|_| Words.generate(&mut rng)).collect();
+
+        assert_eq!(divergence.input, inputs[case]);
+        assert!(inputs[..case]
+            .iter()
+            .all(|input| count_spaces(input) == count_words_slowly(input)));
+    }
+
+    #[test]
+    fn checks_examples_of_manifest() {
+        let differential = || Differential::for_day(day!(12), 2, count_words, count_spaces);
+
+        let divergence = differential().run(&Words).unwrap_err();
+        assert_eq!(divergence.source, Source::Example("12.txt".into()));
+        assert!(divergence.input.starts_with("???.### 1,1,3"));
+
+        let divergence = differential()
+            .filter_examples(|example| example.params.contains_key("unfold"))
+            .run(&Words)
+            .unwrap_err();
+        assert_eq!(
+            divergence.source,
+            Source::Example("12.txt with unfold=2".into())
+        );
+
+        assert!(differential()
+            .filter_examples(|_| false)
+            .with_cases(0)
+            .run(&Words)
+            .is_ok());
+    }
+}
@@ -0,0 +1,214 @@
+/// Module that checks the solutions against the examples listed in a manifest, e.g. `data/examples/11.toml`.
+/// The `solution!` macro generates a test running every example of the manifest of its day, so days do not need to
+/// write their own `test_part_one` and `test_part_two`.
+use std::{collections::BTreeMap, fs, io, path::PathBuf};
+
+use serde::Deserialize;
+
+use crate::template::{
+    answer::Answer,
+    data::{self, DataStore},
+    logging,
+    params::{self, ParamInfo},
+};
+use crate::Day;
+
+/// An example of the manifest: its file, the part it is checked against, and the answer of that part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    /// File name relative to the examples directory, e.g. `08-2.txt`.
+    pub file: String,
+    pub part: u8,
+    pub answer: String,
+    /// Parameters of the solution, see [`params::Param`].
+    pub params: BTreeMap<String, String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ExampleEntry {
+    file: String,
+    part: u8,
+    answer: toml::Value,
+    #[serde(default)]
+    params: BTreeMap<String, toml::Value>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ManifestFile {
+    example: Vec<ExampleEntry>,
+}
+
+/// Answers and parameters are written as numbers or strings, and compared as strings.
+fn scalar(value: toml::Value) -> Result<String, String> {
+    match value {
+        toml::Value::String(s) => Ok(s),
+        toml::Value::Integer(i) => Ok(i.to_string()),
+        other => Err(format!("expected a number or a string, found {other}")),
+    }
+}
+
+/// Parses a manifest, a list of `[[example]]` tables.
+pub fn parse(s: &str) -> Result<Vec<Example>, String> {
+    let file: ManifestFile = toml::from_str(s).map_err(|e| e.to_string())?;
+
+    file.example
+        .into_iter()
+        .map(|entry| {
+            if !(1..=2).contains(&entry.part) {
+                return Err(format!("{}: invalid part {}", entry.file, entry.part));
+            }
+
+            let params = entry
+                .params
+                .into_iter()
+                .map(|(name, value)| Ok((name, scalar(value)?)))
+                .collect::<Result<_, String>>()
+                .map_err(|e| format!("{}: {e}", entry.file))?;
+
+            Ok(Example {
+                answer: scalar(entry.answer).map_err(|e| format!("{}: {e}", entry.file))?,
+                file: entry.file,
+                part: entry.part,
+                params,
+            })
+        })
+        .collect()
+}
+
+/// Path of the manifest of a day, e.g. `data/examples/11.toml`.
+#[must_use]
+pub fn manifest_path(day: Day) -> PathBuf {
+    data::data_path("examples", &format!("{day}.toml"))
+}
+
+/// Loads the examples of a day. A missing manifest means no examples.
+pub fn load(day: Day) -> Result<Vec<Example>, String> {
+    let path = manifest_path(day);
+
+    match fs::read_to_string(&path) {
+        Ok(s) => parse(&s).map_err(|e| format!("{}: {e}", path.display())),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(vec![]),
+        Err(e) => Err(format!("{}: {e}", path.display())),
+    }
+}
+
+/// The answer of a part as written in a manifest, i.e. what would be submitted.
+pub fn answer<T: Answer>(answer: Option<T>) -> Option<String> {
+    answer.map(|answer| answer.submission().unwrap_or_else(|| answer.to_string()))
+}
+
+/// Runs `solve(input, part)` on every example of the day, with the parameters of the example set.
+///
+/// # Panics
+///
+/// With every example whose answer is wrong, if the manifest or an example file cannot be read, or if an example
+/// sets a parameter that the day does not declare.
+pub fn check(day: Day, declared: &[ParamInfo], solve: impl Fn(&str, u8) -> Option<String>) {
+    logging::init_test();
+
+    let examples = load(day).unwrap_or_else(|e| panic!("Invalid example manifest {e}"));
+    let store = DataStore::locate().unwrap_or_else(|e| panic!("{e}"));
+
+    for example in &examples {
+        let values: Vec<(String, String)> = example.params.clone().into_iter().collect();
+        if let Err(e) = params::validate(&values, declared) {
+            panic!("{} part {}: {e}", example.file, example.part);
+        }
+    }
+
+    let failures: Vec<String> = examples
+        .iter()
+        .filter_map(|example| {
+            let input = store
+                .read("examples", &example.file)
+                .unwrap_or_else(|e| panic!("Could not read example: {e}"));
+
+            let result = params::with(&example.params, || solve(&input, example.part));
+
+            (result.as_deref() != Some(example.answer.as_str())).then(|| {
+                format!(
+                    "{} part {}: expected {}, got {}",
+                    example.file,
+                    example.part,
+                    example.answer,
+                    result.unwrap_or_else(|| "no answer".into())
+                )
+            })
+        })
+        .collect();
+
+    assert!(
+        failures.is_empty(),
+        "{} of {} examples failed:\n{}",
+        failures.len(),
+        examples.len(),
+        failures.join("\n")
+    );
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{answer, parse, Example};
+    use crate::template::answer::LetterArt;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn parses_manifest() {
+        let examples = parse(
+            r#"
+            [[example]]
+            file = "11.txt"
+            part = 1
+            answer = 374
+
+            [[example]]
+            file = "11.txt"
+            part = 2
+            answer = "8410"
+            params = { expansion = 100 }
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            examples,
+            [
+                Example {
+                    file: "11.txt".into(),
+                    part: 1,
+                    answer: "374".into(),
+                    params: BTreeMap::new(),
+                },
+                Example {
+                    file: "11.txt".into(),
+                    part: 2,
+                    answer: "8410".into(),
+                    params: BTreeMap::from([("expansion".into(), "100".into())]),
+                },
+            ]
+        );
+        assert_eq!(parse("").unwrap(), []);
+    }
+
+    #[test]
+    fn rejects_invalid_examples() {
+        let example = |fields: &str| parse(&format!("[[example]]\nfile = \"01.txt\"\n{fields}"));
+
+        assert!(example("part = 1\nanswer = 1").is_ok());
+        assert!(example("part = 3\nanswer = 1").is_err());
+        assert!(example("part = 1").is_err());
+        assert!(example("part = 1\nanswer = 1.5").is_err());
+        assert!(example("part = 1\nanswer = 1\nexpected = 1").is_err());
+    }
+
+    #[test]
+    fn formats_answers_like_submissions() {
+        assert_eq!(answer(Some(42_u64)), Some("42".into()));
+        assert_eq!(answer::<u32>(None), None);
+
+        let art = LetterArt(".##.\n#..#\n#..#\n####\n#..#\n#..#".into());
+        assert_eq!(answer(Some(art)), Some("A".into()));
+    }
+}
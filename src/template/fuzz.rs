@@ -0,0 +1,429 @@
+/// Module that fuzzes solutions with random inputs in the grammar of their day.
+/// Days pass a [`Generator`] to `solution!` with `fuzz = <generator>`, whose test hands it to [`check_parts`] with the
+/// parts of the day. Parts run under `catch_unwind` on a thread of their own, so a panic or a part that does not finish
+/// within the timeout is reported with its input, minimised for panics.
+use std::{
+    fmt::Display,
+    panic::{self, AssertUnwindSafe},
+    sync::{mpsc, Arc, Once},
+    thread,
+    time::Duration,
+};
+
+use crate::template::params;
+
+/// Prefix of the names of the threads running parts, whose panics are not printed.
+const THREAD_PREFIX: &str = "fuzz-part-";
+
+/// Seed of the generated inputs, fixed so that failures are reproducible.
+pub const SEED: u64 = 2023;
+
+/// Upper bound of the runs spent on minimising an input.
+const MINIMISE_RUNS: usize = 2000;
+
+/// A small, seeded pseudo-random number generator (SplitMix64), so every run of the fuzzer sees the same inputs.
+pub struct Rng(u64);
+
+impl Rng {
+    #[must_use]
+    pub fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `min..=max`.
+    pub fn range(&mut self, min: u64, max: u64) -> u64 {
+        min + self.next_u64() % (max - min + 1)
+    }
+
+    /// `true` with a probability of `1 / n`.
+    pub fn one_in(&mut self, n: u64) -> bool {
+        self.next_u64().is_multiple_of(n)
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.range(0, items.len() as u64 - 1) as usize]
+    }
+
+    /// A string of `len` characters picked from `alphabet`.
+    pub fn string(&mut self, alphabet: &str, len: usize) -> String {
+        let chars: Vec<char> = alphabet.chars().collect();
+        (0..len).map(|_| *self.choose(&chars)).collect()
+    }
+}
+
+/// Random inputs in the grammar of a day.
+pub trait Generator {
+    fn generate(&self, rng: &mut Rng) -> String;
+}
+
+/// A function is a generator, e.g. `fn games(rng: &mut Rng) -> String` of day 2.
+impl<F: Fn(&mut Rng) -> String> Generator for F {
+    fn generate(&self, rng: &mut Rng) -> String {
+        self(rng)
+    }
+}
+
+/// A part of a solution, taking the raw input. Days with a parse step parse inside of it.
+pub type Part = Arc<dyn Fn(&str) + Send + Sync>;
+
+/// A part of a solution whose answer is dropped.
+pub fn part<T: 'static>(run: fn(&str) -> T) -> Part {
+    Arc::new(move |input| {
+        run(input);
+    })
+}
+
+/// A part of a solution with a parse step, run on the parsed raw input.
+pub fn parsed_part<P: 'static, T: 'static>(parse: fn(&str) -> P, run: fn(&P) -> T) -> Part {
+    Arc::new(move |input| {
+        run(&parse(input));
+    })
+}
+
+/// Why a part failed on an input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Failure {
+    Panic(String),
+    Timeout(Duration),
+}
+
+/// A failing input of a part, with the input it was first found with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Crash {
+    pub part: u8,
+    pub case: usize,
+    pub failure: Failure,
+    pub input: String,
+    pub original: String,
+}
+
+impl Display for Crash {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.failure {
+            Failure::Panic(message) => write!(
+                f,
+                "Part {} panicked on case {}: {message}",
+                self.part, self.case
+            )?,
+            Failure::Timeout(timeout) => write!(
+                f,
+                "Part {} did not finish within {timeout:?} on case {}",
+                self.part, self.case
+            )?,
+        }
+
+        write!(f, "\n--- input ---\n{}\n-------------", self.input)?;
+
+        if self.input != self.original {
+            write!(
+                f,
+                "\nminimised from {} bytes of input.",
+                self.original.len()
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Settings of a fuzzing run. The defaults are fixed so that failures are reproducible.
+#[derive(Debug, Clone, Copy)]
+pub struct Fuzzer {
+    pub seed: u64,
+    /// Number of inputs generated.
+    pub cases: usize,
+    /// Time a part may take on an input.
+    pub timeout: Duration,
+}
+
+impl Default for Fuzzer {
+    fn default() -> Self {
+        Fuzzer {
+            seed: SEED,
+            cases: 200,
+            timeout: Duration::from_secs(2),
+        }
+    }
+}
+
+impl Fuzzer {
+    /// Runs `parts`, numbered from 1, on generated inputs until one fails.
+    pub fn run(&self, generator: &impl Generator, parts: &[Part]) -> Result<(), Crash> {
+        silence_panics();
+
+        let mut rng = Rng::new(self.seed);
+
+        for case in 0..self.cases {
+            let input = generator.generate(&mut rng);
+
+            for (part, run) in (1..).zip(parts) {
+                if let Some(failure) = self.check(run, part, &input) {
+                    let minimised = match failure {
+                        Failure::Panic(_) => self.minimise(run, part, &input),
+                        // minimising timeouts would take a timeout per attempt.
+                        Failure::Timeout(_) => input.clone(),
+                    };
+
+                    return Err(Crash {
+                        part,
+                        case,
+                        failure: self.check(run, part, &minimised).unwrap_or(failure),
+                        input: minimised,
+                        original: input,
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Runs a part on an input, `None` if it finished in time.
+    /// The parameters set on the calling thread with [`params::with`] are set on the thread of the part too.
+    fn check(&self, run: &Part, part: u8, input: &str) -> Option<Failure> {
+        let (sender, receiver) = mpsc::channel();
+        let input = input.to_string();
+        let run = Arc::clone(run);
+        let params = params::current();
+
+        thread::Builder::new()
+            .name(format!("{THREAD_PREFIX}{part}"))
+            .spawn(move || {
+                let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                    params::with(&params, || run(&input));
+                }));
+                let _ = sender.send(result.map_err(|payload| panic_message(&*payload)));
+            })
+            .expect("fuzzing thread can be spawned");
+
+        // a part that times out is left running in the background.
+        match receiver.recv_timeout(self.timeout) {
+            Ok(Ok(())) => None,
+            Ok(Err(message)) => Some(Failure::Panic(message)),
+            Err(_) => Some(Failure::Timeout(self.timeout)),
+        }
+    }
+
+    /// Removes chunks of lines, then of characters, as long as the part keeps panicking.
+    fn minimise(&self, run: &Part, part: u8, input: &str) -> String {
+        let mut runs = 0;
+        let mut panics = |candidate: &str| {
+            runs += 1;
+            runs <= MINIMISE_RUNS
+                && matches!(self.check(run, part, candidate), Some(Failure::Panic(_)))
+        };
+
+        let lines: Vec<String> = input.lines().map(str::to_string).collect();
+        let lines = remove_chunks(lines, |lines| panics(&lines.join("\n")));
+
+        let chars: Vec<char> = lines.join("\n").chars().collect();
+        remove_chunks(chars, |chars| panics(&chars.iter().collect::<String>()))
+            .into_iter()
+            .collect()
+    }
+}
+
+/// Fuzzes `parts`, numbered from 1, with the default settings and panics with the report of the first failure.
+pub fn check(generator: &impl Generator, parts: &[Part]) {
+    if let Err(crash) = Fuzzer::default().run(generator, parts) {
+        panic!("{crash}");
+    }
+}
+
+/// Fuzzes both parts of a day, e.g. `check_parts(&Games, part_one, part_two)`.
+pub fn check_parts<A: 'static, B: 'static>(
+    generator: &impl Generator,
+    part_one: fn(&str) -> A,
+    part_two: fn(&str) -> B,
+) {
+    check(generator, &[part(part_one), part(part_two)]);
+}
+
+/// Fuzzes both parts of a day with a parse step, parsing the input before each part.
+pub fn check_parsed_parts<P: 'static, A: 'static, B: 'static>(
+    generator: &impl Generator,
+    parse: fn(&str) -> P,
+    part_one: fn(&P) -> A,
+    part_two: fn(&P) -> B,
+) {
+    check(
+        generator,
+        &[parsed_part(parse, part_one), parsed_part(parse, part_two)],
+    );
+}
+
+/// Removes ever smaller chunks of `items` while `fails` holds for what is left.
+fn remove_chunks<T: Clone>(mut items: Vec<T>, mut fails: impl FnMut(&[T]) -> bool) -> Vec<T> {
+    let mut chunk = items.len().div_ceil(2).max(1);
+
+    loop {
+        let mut start = 0;
+
+        while start < items.len() {
+            let end = (start + chunk).min(items.len());
+            let candidate: Vec<T> = [&items[..start], &items[end..]].concat();
+
+            if fails(&candidate) {
+                items = candidate;
+            } else {
+                start = end;
+            }
+        }
+
+        if chunk == 1 {
+            return items;
+        }
+        chunk = chunk.div_ceil(2);
+    }
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| (*s).to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".into())
+}
+
+/// Keeps the panics of fuzzed parts out of the test output, they are reported by the fuzzer.
+fn silence_panics() {
+    static HOOK: Once = Once::new();
+
+    HOOK.call_once(|| {
+        let default = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            let is_fuzzed = thread::current()
+                .name()
+                .is_some_and(|name| name.starts_with(THREAD_PREFIX));
+            if !is_fuzzed {
+                default(info);
+            }
+        }));
+    });
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{check_parsed_parts, part, Failure, Fuzzer, Generator, Rng};
+    use crate::template::params::{self, Param};
+    use std::{collections::BTreeMap, time::Duration};
+
+    struct Numbers;
+
+    impl Generator for Numbers {
+        fn generate(&self, rng: &mut Rng) -> String {
+            (0..rng.range(1, 8))
+                .map(|_| rng.range(0, 20).to_string())
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+    }
+
+    fn fails_on_thirteen(input: &str) {
+        for line in input.lines() {
+            assert_ne!(line, "13", "unlucky number");
+        }
+    }
+
+    #[test]
+    fn generates_same_inputs() {
+        let inputs = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..10)
+                .map(|_| Numbers.generate(&mut rng))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(inputs(1), inputs(1));
+        assert_ne!(inputs(1), inputs(2));
+    }
+
+    #[test]
+    fn passes_sound_parts() {
+        let fuzzer = Fuzzer::default();
+        assert!(fuzzer
+            .run(
+                &Numbers,
+                &[part(|_| {}), part(|input| input.lines().count())]
+            )
+            .is_ok());
+    }
+
+    #[test]
+    fn minimises_panics() {
+        let crash = Fuzzer::default()
+            .run(&Numbers, &[part(|_| {}), part(fails_on_thirteen)])
+            .unwrap_err();
+
+        assert_eq!(crash.part, 2);
+        assert_eq!(crash.input, "13");
+        assert!(crash.original.lines().any(|line| line == "13"));
+        assert!(
+            matches!(crash.failure, Failure::Panic(ref message) if message.contains("unlucky number"))
+        );
+    }
+
+    #[test]
+    fn sets_params_on_part_threads() {
+        const LIMIT: Param<usize> = Param::new("limit", 100, "Most numbers per input.");
+        let params = BTreeMap::from([("limit".to_string(), "0".to_string())]);
+
+        let crash = params::with(&params, || {
+            Fuzzer::default().run(
+                &Numbers,
+                &[part(|input| assert!(input.lines().count() <= LIMIT.get()))],
+            )
+        })
+        .unwrap_err();
+
+        assert_eq!(crash.case, 0);
+    }
+
+    #[test]
+    fn generates_with_functions() {
+        let digit = |rng: &mut Rng| rng.range(0, 9).to_string();
+        assert_eq!(digit.generate(&mut Rng::new(1)).len(), 1);
+    }
+
+    #[test]
+    fn reports_timeouts() {
+        let fuzzer = Fuzzer {
+            timeout: Duration::from_millis(50),
+            ..Fuzzer::default()
+        };
+        let crash = fuzzer
+            .run(
+                &Numbers,
+                &[part(|_| std::thread::sleep(Duration::from_secs(1)))],
+            )
+            .unwrap_err();
+
+        assert_eq!(crash.case, 0);
+        assert_eq!(crash.failure, Failure::Timeout(Duration::from_millis(50)));
+        assert_eq!(crash.input, crash.original);
+    }
+
+    #[test]
+    #[should_panic(expected = "Part 2 panicked")]
+    fn checks_parsed_parts() {
+        check_parsed_parts(
+            &Numbers,
+            |input| {
+                input
+                    .lines()
+                    .map(|line| line.parse::<u32>().unwrap())
+                    .collect::<Vec<_>>()
+            },
+            |numbers| numbers.len(),
+            |numbers| assert!(!numbers.contains(&13)),
+        );
+    }
+}
@@ -0,0 +1,267 @@
+/// Module that installs the `tracing` subscriber of day binaries and their tests.
+/// Log lines go to stderr and are tagged with the `day` and `part` fields of the span of the part being solved.
+/// Other threads, e.g. `rayon` workers, are tagged once they enter that span with `Span::current().in_scope(...)`.
+use std::{
+    fmt, io,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use tracing::{
+    field::{Field, Visit},
+    span, Event, Subscriber,
+};
+use tracing_subscriber::{
+    filter::DynFilterFn,
+    fmt::{format::Writer, FmtContext, FormatEvent, FormatFields},
+    layer::{Context, Layer},
+    prelude::*,
+    registry::LookupSpan,
+    EnvFilter,
+};
+
+use crate::Day;
+
+static MUTED: AtomicBool = AtomicBool::new(false);
+
+/// Part number used for the parse phase of two-phase solutions.
+pub const PARSE_PHASE: u8 = 0;
+
+/// Installs the subscriber of a day binary. `filter` comes from `--log` and takes precedence over `RUST_LOG`;
+/// without either, nothing is installed and logging costs nothing.
+pub fn init(filter: Option<&str>) {
+    let Some(directives) = filter
+        .map(String::from)
+        .or_else(|| std::env::var(EnvFilter::DEFAULT_ENV).ok())
+    else {
+        return;
+    };
+
+    let filter = EnvFilter::try_new(directives).unwrap_or_else(|e| {
+        eprintln!("Invalid log filter: {e}");
+        std::process::exit(1);
+    });
+
+    let _ = tracing_subscriber::registry()
+        .with(filter)
+        // dynamic, so that the result is not cached per callsite.
+        .with(DynFilterFn::new(|_, _| !MUTED.load(Ordering::Relaxed)))
+        .with(PartTags)
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_writer(io::stderr)
+                .event_format(PartFormat),
+        )
+        .try_init();
+}
+
+/// Installs a subscriber for unit tests, once per test binary. Output is captured by the test harness and
+/// shown for failing tests. Filtered by `RUST_LOG`, `debug` by default.
+pub fn init_test() {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("debug"));
+
+    let _ = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_test_writer()
+        .try_init();
+}
+
+/// Enters the span of a part, whose `day` and `part` fields tag the log lines until the guard is dropped.
+/// Called by the runner.
+pub fn enter_part(day: Day, part: u8) -> span::EnteredSpan {
+    let day = day.into_inner();
+
+    if part == PARSE_PHASE {
+        tracing::info_span!("parse", day).entered()
+    } else {
+        tracing::info_span!("part", day, part).entered()
+    }
+}
+
+/// Runs `f` with all logging disabled, e.g. while benchmarking. Logging is restored even if `f` panics.
+pub fn muted<T>(f: impl FnOnce() -> T) -> T {
+    let _guard = MuteGuard(MUTED.swap(true, Ordering::Relaxed));
+    f()
+}
+
+/// Restores the previous muting when dropped.
+struct MuteGuard(bool);
+
+impl Drop for MuteGuard {
+    fn drop(&mut self) {
+        MUTED.store(self.0, Ordering::Relaxed);
+    }
+}
+
+/// The `day` and `part` fields of a span, stored in its extensions by [`PartTags`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct PartTag {
+    day: Option<u64>,
+    part: Option<u64>,
+    is_parse: bool,
+}
+
+impl PartTag {
+    /// The tag of log lines, e.g. `day 11 part 1`.
+    fn label(self) -> Option<String> {
+        match (self.day?, self.part) {
+            (day, _) if self.is_parse => Some(format!("day {day:02} parse")),
+            (day, Some(part)) => Some(format!("day {day:02} part {part}")),
+            (day, None) => Some(format!("day {day:02}")),
+        }
+    }
+}
+
+impl Visit for PartTag {
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        match field.name() {
+            "day" => self.day = Some(value),
+            "part" => self.part = Some(value),
+            _ => {}
+        }
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        if let Ok(value) = u64::try_from(value) {
+            self.record_u64(field, value);
+        }
+    }
+
+    fn record_debug(&mut self, _: &Field, _: &dyn fmt::Debug) {}
+}
+
+/// Records the [`PartTag`] of the `part` and `parse` spans.
+struct PartTags;
+
+impl<S> Layer<S> for PartTags
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, S>) {
+        let name = attrs.metadata().name();
+        if name != "part" && name != "parse" {
+            return;
+        }
+
+        let mut tag = PartTag {
+            is_parse: name == "parse",
+            ..PartTag::default()
+        };
+        attrs.record(&mut tag);
+
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(tag);
+        }
+    }
+}
+
+/// The tag of the innermost part span around an event.
+fn event_tag<S, N>(ctx: &FmtContext<'_, S, N>) -> Option<String>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    ctx.event_scope()?
+        .find_map(|span| span.extensions().get::<PartTag>().copied())
+        .and_then(PartTag::label)
+}
+
+/// `LEVEL [day NN part N] target: message fields`.
+struct PartFormat;
+
+impl<S, N> FormatEvent<S, N> for PartFormat
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        let metadata = event.metadata();
+
+        write!(writer, "{:>5} ", metadata.level())?;
+        if let Some(tag) = event_tag(ctx) {
+            write!(writer, "[{tag}] ")?;
+        }
+        write!(writer, "{}: ", metadata.target())?;
+
+        ctx.format_fields(writer.by_ref(), event)?;
+        writeln!(writer)
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{enter_part, muted, PartFormat, PartTags, MUTED, PARSE_PHASE};
+    use crate::day;
+    use std::{
+        io, panic,
+        sync::{atomic::Ordering, Arc, Mutex},
+    };
+    use tracing_subscriber::prelude::*;
+
+    /// Log lines written by `f`.
+    fn logged(f: impl FnOnce()) -> String {
+        let buffer = Arc::new(Mutex::new(vec![]));
+        let writer = {
+            let buffer = Arc::clone(&buffer);
+            move || BufferWriter(Arc::clone(&buffer))
+        };
+
+        let subscriber = tracing_subscriber::registry().with(PartTags).with(
+            tracing_subscriber::fmt::layer()
+                .with_writer(writer)
+                .event_format(PartFormat),
+        );
+        tracing::subscriber::with_default(subscriber, f);
+
+        let bytes = buffer.lock().unwrap().clone();
+        String::from_utf8(bytes).unwrap()
+    }
+
+    struct BufferWriter(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for BufferWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn tags_lines_with_span_fields() {
+        let output = logged(|| {
+            {
+                let _guard = enter_part(day!(11), 2);
+                tracing::info!("inside");
+            }
+            tracing::info!("outside");
+
+            let _guard = enter_part(day!(8), PARSE_PHASE);
+            tracing::info!("parsing");
+        });
+
+        let lines: Vec<&str> = output.lines().collect();
+        assert!(
+            lines[0].ends_with("[day 11 part 2] advent_of_code::template::logging::tests: inside")
+        );
+        assert!(lines[1].ends_with(" advent_of_code::template::logging::tests: outside"));
+        assert!(lines[2].contains("[day 08 parse]"));
+    }
+
+    #[test]
+    fn unmutes_after_panics() {
+        let was_muted = muted(|| MUTED.load(Ordering::Relaxed));
+        assert!(was_muted);
+        assert!(!MUTED.load(Ordering::Relaxed));
+
+        let result = panic::catch_unwind(|| muted(|| panic!("benchmark failed")));
+        assert!(result.is_err());
+        assert!(!MUTED.load(Ordering::Relaxed));
+    }
+}
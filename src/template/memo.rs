@@ -0,0 +1,210 @@
+/// Module that provides scoped memoisation caches for solutions.
+/// A [`Memo`] lives as long as the value that owns it (usually a single `part_one` / `part_two` call),
+/// so repeated benchmark iterations always start from a cold cache and parts never share state.
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::AddAssign;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, RwLock};
+
+/// Hit / miss counters of one or more [`Memo`] caches.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl MemoStats {
+    /// Total number of lookups performed.
+    #[must_use]
+    pub fn lookups(&self) -> u64 {
+        self.hits + self.misses
+    }
+
+    /// Share of lookups that were answered by the cache, in range `0.0..=1.0`.
+    #[must_use]
+    pub fn hit_rate(&self) -> f64 {
+        if self.lookups() == 0 {
+            return 0.0;
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let rate = self.hits as f64 / self.lookups() as f64;
+        rate
+    }
+}
+
+impl AddAssign for MemoStats {
+    fn add_assign(&mut self, other: Self) {
+        self.hits += other.hits;
+        self.misses += other.misses;
+    }
+}
+
+/// Statistics of all caches dropped since the last call to [`take_stats`].
+static COLLECTED: Mutex<MemoStats> = Mutex::new(MemoStats { hits: 0, misses: 0 });
+
+/// Returns the statistics of every [`Memo`] dropped since the last call and resets them.
+/// The runner uses this to report cache efficiency after each part.
+pub fn take_stats() -> MemoStats {
+    let mut collected = COLLECTED.lock().unwrap();
+    std::mem::take(&mut *collected)
+}
+
+/// A thread-safe memoisation cache that can be shared by reference between rayon workers.
+///
+/// The lock is never held while a value is being computed, so recursive functions can
+/// consult the same cache from within [`Memo::get_or_insert_with`].
+///
+/// ```
+/// # use advent_of_code::template::memo::Memo;
+/// fn fib(n: u64, memo: &Memo<u64, u64>) -> u64 {
+///     if n < 2 {
+///         return n;
+///     }
+///     memo.get_or_insert_with(n, || fib(n - 1, memo) + fib(n - 2, memo))
+/// }
+///
+/// let memo = Memo::new();
+/// assert_eq!(fib(50, &memo), 12_586_269_025);
+/// ```
+pub struct Memo<K, V> {
+    cache: RwLock<HashMap<K, V>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl<K: Eq + Hash, V: Clone> Memo<K, V> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            cache: RwLock::new(HashMap::new()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Looks up a cached value, counting the lookup as a hit or a miss.
+    pub fn get<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        let value = self.cache.read().unwrap().get(key).cloned();
+
+        if value.is_some() {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+        }
+
+        value
+    }
+
+    /// Stores a value, replacing any previous value for the same key.
+    pub fn insert(&self, key: K, value: V) {
+        self.cache.write().unwrap().insert(key, value);
+    }
+
+    /// Returns the cached value for `key`, computing and storing it with `f` on a miss.
+    pub fn get_or_insert_with(&self, key: K, f: impl FnOnce() -> V) -> V {
+        if let Some(value) = self.get(&key) {
+            return value;
+        }
+
+        let value = f();
+        self.insert(key, value.clone());
+        value
+    }
+
+    /// Number of cached entries.
+    pub fn len(&self) -> usize {
+        self.cache.read().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Hit / miss counters of this cache.
+    pub fn stats(&self) -> MemoStats {
+        MemoStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl<K: Eq + Hash, V: Clone> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> Drop for Memo<K, V> {
+    fn drop(&mut self) {
+        let stats = MemoStats {
+            hits: *self.hits.get_mut(),
+            misses: *self.misses.get_mut(),
+        };
+
+        if let Ok(mut collected) = COLLECTED.lock() {
+            *collected += stats;
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{take_stats, Memo, MemoStats};
+    use rayon::prelude::*;
+
+    #[test]
+    fn counts_hits_and_misses() {
+        let memo: Memo<u32, u32> = Memo::new();
+
+        assert_eq!(memo.get(&1), None);
+        memo.insert(1, 10);
+        assert_eq!(memo.get(&1), Some(10));
+        assert_eq!(memo.get_or_insert_with(1, || unreachable!()), 10);
+        assert_eq!(memo.get_or_insert_with(2, || 20), 20);
+
+        assert_eq!(memo.len(), 2);
+        assert_eq!(memo.stats(), MemoStats { hits: 2, misses: 2 });
+    }
+
+    #[test]
+    fn looks_up_borrowed_keys() {
+        let memo: Memo<String, usize> = Memo::new();
+        memo.insert("AAA".to_string(), 1);
+        assert_eq!(memo.get("AAA"), Some(1));
+    }
+
+    #[test]
+    fn is_shareable_between_threads() {
+        let memo: Memo<u64, u64> = Memo::new();
+
+        let sum: u64 = (0..1000_u64)
+            .into_par_iter()
+            .map(|i| memo.get_or_insert_with(i % 10, || i % 10))
+            .sum();
+
+        assert_eq!(sum, 4500);
+        assert_eq!(memo.len(), 10);
+        assert_eq!(memo.stats().lookups(), 1000);
+    }
+
+    #[test]
+    fn reports_stats_on_drop() {
+        let memo: Memo<u32, u32> = Memo::new();
+        memo.get_or_insert_with(1, || 1);
+        memo.get_or_insert_with(1, || 1);
+        drop(memo);
+
+        // other tests may drop caches concurrently, so only a lower bound is stable.
+        let stats = take_stats();
+        assert!(stats.hits >= 1);
+        assert!(stats.misses >= 1);
+    }
+}
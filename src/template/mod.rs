@@ -1,48 +1,129 @@
 use crate::Day;
-use std::{env, fs};
 
+pub mod answer;
 pub mod aoc_cli;
 pub mod commands;
+pub mod config;
+pub mod data;
+pub mod differential;
+pub mod examples;
+pub mod fuzz;
+pub mod logging;
+pub mod memo;
+pub mod params;
+pub mod progress;
+pub mod readme;
 pub mod readme_benchmarks;
+pub mod readme_languages;
+pub mod readme_notes;
+pub mod readme_stars;
 pub mod runner;
+pub mod stats;
+pub mod viz;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
-/// Helper function that reads a text file to a string.
+/// Helper function that reads the file of a day to a string, e.g. `read_file("examples", DAY)`.
+///
+/// # Panics
+///
+/// With the path of the file if it cannot be read, [`data::DataStore`] returns the error instead.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    data::DataStore::locate()
+        .and_then(|store| store.read_day(folder, day))
+        .unwrap_or_else(|e| panic!("{e}"))
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
+///
+/// # Panics
+///
+/// With the path of the file if it cannot be read.
 #[must_use]
 pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    data::DataStore::locate()
+        .and_then(|store| store.read_day_part(folder, day, part))
+        .unwrap_or_else(|e| panic!("{e}"))
 }
 
 /// Creates the constant `DAY` and sets up the input and runner for each part.
+/// With `parse = <fn>`, the input is parsed once by `fn(&str) -> Input` and the parts take `&Input`.
+/// With `params = [<const>, ...]`, the [`params::Param`] constants of the day can be set with `--param name=value`.
+/// With `fuzz = <generator>`, the test `fuzz_parts` of the `fuzz` feature fuzzes the parts with a [`fuzz::Generator`].
+/// Also generates the test `test_examples`, which checks the parts against the example manifest of the day.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
+    ($day:expr, parse = $parse:path $(, params = [$($param:path),* $(,)?])? $(, fuzz = $generator:path)?) => {
         /// The current day.
         const DAY: advent_of_code::Day = advent_of_code::day!($day);
 
         fn main() {
             use advent_of_code::template::runner::*;
-            let input = advent_of_code::template::read_file("inputs", DAY);
-            run_part(part_one, &input, DAY, 1);
-            run_part(part_two, &input, DAY, 2);
+            let options = RunOptions::from_env();
+            init_params(&options, &[$($($param.info()),*)?]);
+            advent_of_code::template::logging::init(options.log.as_deref());
+            let input = options.read_input(DAY);
+            let parsed = run_parse($parse, &input, DAY, &options);
+            run_part(part_one, &parsed, DAY, 1, &options);
+            run_part(part_two, &parsed, DAY, 2, &options);
+            print_peak_memory(&options);
         }
+
+        #[cfg(test)]
+        #[test]
+        fn test_examples() {
+            use advent_of_code::template::examples;
+            examples::check(DAY, &[$($($param.info()),*)?], |input, part| {
+                let parsed = $parse(input);
+                match part {
+                    1 => examples::answer(part_one(&parsed)),
+                    _ => examples::answer(part_two(&parsed)),
+                }
+            });
+        }
+
+        $(
+            #[cfg(all(test, feature = "fuzz"))]
+            #[test]
+            fn fuzz_parts() {
+                advent_of_code::template::fuzz::check_parsed_parts(&$generator, $parse, part_one, part_two);
+            }
+        )?
+    };
+    ($day:expr $(, params = [$($param:path),* $(,)?])? $(, fuzz = $generator:path)?) => {
+        /// The current day.
+        const DAY: advent_of_code::Day = advent_of_code::day!($day);
+
+        fn main() {
+            use advent_of_code::template::runner::*;
+            let options = RunOptions::from_env();
+            init_params(&options, &[$($($param.info()),*)?]);
+            advent_of_code::template::logging::init(options.log.as_deref());
+            let input = options.read_input(DAY);
+            run_part(part_one, &input, DAY, 1, &options);
+            run_part(part_two, &input, DAY, 2, &options);
+            print_peak_memory(&options);
+        }
+
+        #[cfg(test)]
+        #[test]
+        fn test_examples() {
+            use advent_of_code::template::examples;
+            examples::check(DAY, &[$($($param.info()),*)?], |input, part| match part {
+                1 => examples::answer(part_one(input)),
+                _ => examples::answer(part_two(input)),
+            });
+        }
+
+        $(
+            #[cfg(all(test, feature = "fuzz"))]
+            #[test]
+            fn fuzz_parts() {
+                advent_of_code::template::fuzz::check_parts(&$generator, part_one, part_two);
+            }
+        )?
     };
 }
@@ -0,0 +1,212 @@
+/// Module that passes named parameters to solutions, e.g. the expansion factor of day 11.
+/// Examples often use other constants than the real input. Days declare these as [`Param`] constants with the value
+/// of the real puzzle as default, which `--param name=value` overrides on the command line and the examples of a day
+/// override in their manifest.
+use std::{
+    cell::RefCell,
+    collections::BTreeMap,
+    fmt::{Debug, Display},
+    str::FromStr,
+    sync::OnceLock,
+};
+
+thread_local! {
+    static PARAMS: RefCell<BTreeMap<String, String>> = const { RefCell::new(BTreeMap::new()) };
+}
+
+/// Values of `--param`, set once by the runner.
+static OVERRIDES: OnceLock<BTreeMap<String, String>> = OnceLock::new();
+
+/// A named parameter of a solution, e.g.
+/// `const EXPANSION: Param<usize> = Param::new("expansion", 1_000_000, "How much wider empty lines get.");`
+/// The default is the value of the real puzzle.
+#[derive(Debug, Clone, Copy)]
+pub struct Param<T> {
+    pub name: &'static str,
+    pub default: T,
+    pub description: &'static str,
+}
+
+/// A declared parameter regardless of its type, to validate and list parameters.
+#[derive(Debug, Clone)]
+pub struct ParamInfo {
+    pub name: &'static str,
+    pub default: String,
+    pub description: &'static str,
+    /// Whether a value parses, with the parse error if not.
+    pub validate: fn(&str) -> Result<(), String>,
+}
+
+impl<T> Param<T>
+where
+    T: FromStr + Display + Clone,
+    T::Err: Debug,
+{
+    pub const fn new(name: &'static str, default: T, description: &'static str) -> Self {
+        Param {
+            name,
+            default,
+            description,
+        }
+    }
+
+    /// The value of the parameter. Read it at the start of a part rather than in parallel iterators, values set by
+    /// tests only apply to their thread.
+    #[must_use]
+    pub fn get(&self) -> T {
+        get(self.name, self.default.clone())
+    }
+
+    #[must_use]
+    pub fn info(&self) -> ParamInfo {
+        ParamInfo {
+            name: self.name,
+            default: self.default.to_string(),
+            description: self.description,
+            validate: |value| value.parse::<T>().map(|_| ()).map_err(|e| format!("{e:?}")),
+        }
+    }
+}
+
+/// Checks `values` against the declared parameters: every name must be declared and every value must parse.
+pub fn validate(values: &[(String, String)], declared: &[ParamInfo]) -> Result<(), String> {
+    for (name, value) in values {
+        let Some(param) = declared.iter().find(|param| param.name == name) else {
+            return Err(if declared.is_empty() {
+                format!("Unknown parameter {name}, this day has no parameters.")
+            } else {
+                let params: Vec<String> = declared
+                    .iter()
+                    .map(|param| {
+                        format!(
+                            "  {}: {} [default: {}]",
+                            param.name, param.description, param.default
+                        )
+                    })
+                    .collect();
+                format!(
+                    "Unknown parameter {name}, expected one of:\n{}",
+                    params.join("\n")
+                )
+            });
+        };
+
+        (param.validate)(value)
+            .map_err(|e| format!("Invalid value of parameter {name}: {value} ({e})"))?;
+    }
+
+    Ok(())
+}
+
+/// Sets the values of `--param` for the whole process, after validating them.
+pub fn init(values: &[(String, String)], declared: &[ParamInfo]) -> Result<(), String> {
+    validate(values, declared)?;
+    let _ = OVERRIDES.set(values.iter().cloned().collect());
+    Ok(())
+}
+
+/// Runs `f` with `params` set on the current thread, e.g. for an example of the manifest.
+/// The previous values are restored even if `f` panics.
+pub fn with<R>(params: &BTreeMap<String, String>, f: impl FnOnce() -> R) -> R {
+    let _guard = ParamsGuard(PARAMS.with(|p| p.replace(params.clone())));
+    f()
+}
+
+/// Restores the previous parameters of the thread when dropped.
+struct ParamsGuard(BTreeMap<String, String>);
+
+impl Drop for ParamsGuard {
+    fn drop(&mut self) {
+        let previous = std::mem::take(&mut self.0);
+        PARAMS.with(|p| p.replace(previous));
+    }
+}
+
+/// The parameters set on the current thread with [`with`], to set them on threads it spawns.
+#[must_use]
+pub fn current() -> BTreeMap<String, String> {
+    PARAMS.with(|p| p.borrow().clone())
+}
+
+/// The value of a parameter: set on the current thread, else set by `--param`, else `default`.
+///
+/// # Panics
+///
+/// If the parameter is set to a value that does not parse as `T`.
+pub fn get<T>(name: &str, default: T) -> T
+where
+    T: FromStr,
+    T::Err: Debug,
+{
+    let value = PARAMS
+        .with(|p| p.borrow().get(name).cloned())
+        .or_else(|| OVERRIDES.get().and_then(|o| o.get(name).cloned()));
+
+    value.map_or(default, |value| {
+        value
+            .parse()
+            .unwrap_or_else(|e| panic!("invalid value of parameter {name}: {value} ({e:?})"))
+    })
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{get, validate, with, Param};
+    use std::collections::BTreeMap;
+
+    const EXPANSION: Param<u64> = Param::new("expansion", 2, "How much wider empty lines get.");
+
+    fn values(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|&(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn overrides_defaults_within_scope() {
+        let params = BTreeMap::from([("expansion".to_string(), "100".to_string())]);
+
+        assert_eq!(EXPANSION.get(), 2);
+        assert_eq!(with(&params, || EXPANSION.get()), 100);
+        assert_eq!(with(&params, || get("other", 'a')), 'a');
+        assert_eq!(EXPANSION.get(), 2);
+    }
+
+    #[test]
+    fn restores_params_after_panics() {
+        let params = BTreeMap::from([("expansion".to_string(), "100".to_string())]);
+
+        let result = std::panic::catch_unwind(|| with(&params, || panic!("part failed")));
+
+        assert!(result.is_err());
+        assert_eq!(EXPANSION.get(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid value of parameter expansion")]
+    fn rejects_invalid_values() {
+        let params = BTreeMap::from([("expansion".to_string(), "wide".to_string())]);
+        with(&params, || EXPANSION.get());
+    }
+
+    #[test]
+    fn validates_declared_params() {
+        let declared = [EXPANSION.info()];
+
+        assert!(validate(&values(&[("expansion", "100")]), &declared).is_ok());
+        assert!(validate(&[], &[]).is_ok());
+
+        let err = validate(&values(&[("expansion", "-1")]), &declared).unwrap_err();
+        assert!(err.starts_with("Invalid value of parameter expansion: -1"));
+
+        let err = validate(&values(&[("factor", "100")]), &declared).unwrap_err();
+        assert_eq!(
+            err,
+            "Unknown parameter factor, expected one of:\n  expansion: How much wider empty lines get. [default: 2]"
+        );
+
+        let err = validate(&values(&[("factor", "100")]), &[]).unwrap_err();
+        assert_eq!(err, "Unknown parameter factor, this day has no parameters.");
+    }
+}
@@ -0,0 +1,65 @@
+/// Module that hands out progress bars to solutions.
+/// The runner decides whether bars are drawn: they are hidden with `--time`, JSON output or when stdout is not a
+/// terminal, and cleared before results are printed.
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    OnceLock,
+};
+
+use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget, ProgressStyle};
+
+static VISIBLE: AtomicBool = AtomicBool::new(false);
+
+fn multi() -> &'static MultiProgress {
+    static MULTI: OnceLock<MultiProgress> = OnceLock::new();
+    MULTI.get_or_init(|| MultiProgress::with_draw_target(ProgressDrawTarget::stderr()))
+}
+
+/// A progress bar of `len` steps, to be used with `.progress_with(bar)` of `indicatif`'s iterator traits.
+/// Outside of the runner, e.g. in tests, bars are always hidden.
+#[must_use]
+pub fn bar(len: u64) -> ProgressBar {
+    if !VISIBLE.load(Ordering::Relaxed) {
+        return ProgressBar::hidden();
+    }
+
+    let style =
+        ProgressStyle::with_template("{spinner} [{elapsed_precise}] {wide_bar} {pos}/{len}")
+            .expect("progress bar template is valid");
+
+    multi().add(ProgressBar::new(len).with_style(style))
+}
+
+/// Shows or hides the bars created from now on. Called by the runner.
+pub fn set_visible(visible: bool) {
+    VISIBLE.store(visible, Ordering::Relaxed);
+}
+
+#[must_use]
+pub fn is_visible() -> bool {
+    VISIBLE.load(Ordering::Relaxed)
+}
+
+/// Removes the bars drawn so far from the terminal. Called by the runner before printing a result.
+pub fn clear() {
+    if is_visible() {
+        let _ = multi().clear();
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{bar, set_visible};
+
+    #[test]
+    fn hides_bars_unless_visible() {
+        assert!(bar(10).is_hidden());
+
+        set_visible(true);
+        let visible = bar(10);
+        set_visible(false);
+
+        assert_eq!(visible.length(), Some(10));
+        visible.finish_and_clear();
+    }
+}
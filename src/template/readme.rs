@@ -0,0 +1,239 @@
+/// Module that rewrites the sections of the readme generated by the template.
+/// A section is the text between two occurrences of its marker, e.g. `<!--- benchmarking table --->`. Sections are
+/// replaced in memory and written at once, leaving everything outside of them byte for byte as it was.
+use std::{
+    fmt::Display,
+    fs, io,
+    ops::Range,
+    path::{Path, PathBuf},
+};
+
+use similar::TextDiff;
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "{e}"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+/// Marker of a named section, e.g. `<!--- day 05 notes --->`.
+#[must_use]
+pub fn marker(name: &str) -> String {
+    format!("<!--- {name} --->")
+}
+
+/// Finds the section surrounded by two occurrences of `marker`, markers included.
+fn locate_section(s: &str, marker: &str) -> Result<Range<usize>, Error> {
+    let matches: Vec<_> = s.match_indices(marker).collect();
+
+    match matches[..] {
+        [] => Err(Error::Parser(format!(
+            "{marker}: could not find marker in README."
+        ))),
+        [_] => Err(Error::Parser(format!(
+            "{marker}: could not find closing marker in README."
+        ))),
+        [(start, _), (end, _)] => Ok(start..end + marker.len()),
+        _ => Err(Error::Parser(format!(
+            "{marker}: too many occurrences of marker in README."
+        ))),
+    }
+}
+
+/// The heading level of a section, e.g. `###`, taken from its first heading. `##` for sections without heading.
+pub fn heading_prefix(s: &str, marker: &str) -> Result<String, Error> {
+    let section = locate_section(s, marker)?;
+
+    Ok(s[section.start + marker.len()..section.end - marker.len()]
+        .lines()
+        .map(str::trim_start)
+        .find_map(|line| {
+            let hashes = line.len() - line.trim_start_matches('#').len();
+            (hashes > 0 && line[hashes..].starts_with(' ')).then(|| line[..hashes].to_string())
+        })
+        .unwrap_or_else(|| "##".into()))
+}
+
+/// Replaces the contents of a section with `body`, keeping its markers.
+pub fn replace_section(s: &mut String, marker: &str, body: &str) -> Result<(), Error> {
+    let section = locate_section(s, marker)?;
+    s.replace_range(section, &format!("{marker}\n{body}\n{marker}"));
+    Ok(())
+}
+
+/// A readme file with pending changes to its sections.
+pub struct Readme {
+    path: PathBuf,
+    original: String,
+    content: String,
+}
+
+impl Readme {
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let original = String::from_utf8_lossy(&fs::read(path)?).to_string();
+
+        Ok(Readme {
+            path: path.into(),
+            content: original.clone(),
+            original,
+        })
+    }
+
+    #[must_use]
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+
+    /// The content with pending changes, to edit with [`replace_section`].
+    pub fn content_mut(&mut self) -> &mut String {
+        &mut self.content
+    }
+
+    /// Replaces a section with the result of `build`, which receives the heading prefix of the section.
+    pub fn update_section(
+        &mut self,
+        marker: &str,
+        build: impl FnOnce(&str) -> String,
+    ) -> Result<(), Error> {
+        let prefix = heading_prefix(&self.content, marker)?;
+        replace_section(&mut self.content, marker, &build(&prefix))
+    }
+
+    #[must_use]
+    pub fn is_changed(&self) -> bool {
+        self.original != self.content
+    }
+
+    /// Unified diff of the pending changes, empty if nothing changed.
+    #[must_use]
+    pub fn diff(&self) -> String {
+        let path = self.path.display().to_string();
+
+        TextDiff::from_lines(&self.original, &self.content)
+            .unified_diff()
+            .context_radius(3)
+            .header(&format!("a/{path}"), &format!("b/{path}"))
+            .to_string()
+    }
+
+    /// Writes the pending changes, or prints them as a diff if `dry_run` is set. Returns whether anything changed.
+    /// The file is replaced atomically: the content is written next to it first, then renamed over it.
+    pub fn save(&self, dry_run: bool) -> Result<bool, Error> {
+        if !self.is_changed() {
+            return Ok(false);
+        }
+
+        if dry_run {
+            print!("{}", self.diff());
+            return Ok(true);
+        }
+
+        let file_name = self
+            .path
+            .file_name()
+            .ok_or_else(|| Error::Parser(format!("{}: not a file.", self.path.display())))?;
+        let tmp_path = self
+            .path
+            .with_file_name(format!(".{}.tmp", file_name.to_string_lossy()));
+
+        fs::write(&tmp_path, &self.content)?;
+        fs::rename(&tmp_path, &self.path).inspect_err(|_| {
+            let _ = fs::remove_file(&tmp_path);
+        })?;
+
+        Ok(true)
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{heading_prefix, marker, replace_section, Readme};
+    use std::fs;
+
+    const STARS: &str = "<!--- stars --->";
+
+    #[test]
+    fn replaces_sections() {
+        let mut s = format!("a\n{STARS}\nold\n{STARS}\nb\n<!--- notes --->\n<!--- notes --->\n");
+        replace_section(&mut s, STARS, "new").unwrap();
+        replace_section(&mut s, &marker("notes"), "more\nlines").unwrap();
+
+        assert_eq!(
+            s,
+            format!(
+                "a\n{STARS}\nnew\n{STARS}\nb\n<!--- notes --->\nmore\nlines\n<!--- notes --->\n"
+            )
+        );
+    }
+
+    #[test]
+    fn preserves_surrounding_content() {
+        let outside = "  trailing spaces  \r\n\ttabs\n\n";
+        let mut s = format!("{outside}{STARS}old{STARS}{outside}");
+        replace_section(&mut s, STARS, "new").unwrap();
+
+        assert_eq!(s, format!("{outside}{STARS}\nnew\n{STARS}{outside}"));
+    }
+
+    #[test]
+    fn rejects_unbalanced_markers() {
+        for s in ["", STARS, &STARS.repeat(3)] {
+            let err = replace_section(&mut s.to_string(), STARS, "new").unwrap_err();
+            assert!(err.to_string().starts_with(STARS));
+        }
+    }
+
+    #[test]
+    fn keeps_heading_level() {
+        let s = format!("{STARS}\n### Results\n## Other\n{STARS}\n# Readme\n");
+        assert_eq!(heading_prefix(&s, STARS).unwrap(), "###");
+
+        let s = format!("# Readme\n{STARS}\n#hashtag\n{STARS}");
+        assert_eq!(heading_prefix(&s, STARS).unwrap(), "##");
+    }
+
+    #[test]
+    fn saves_atomically_with_dry_run() {
+        let dir = std::env::temp_dir().join(format!("aoc-readme-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("README.md");
+        fs::write(&path, format!("intro\n{STARS}\n### Stars\nold\n{STARS}\n")).unwrap();
+
+        let mut readme = Readme::load(&path).unwrap();
+        readme
+            .update_section(STARS, |prefix| format!("{prefix} Stars\nnew"))
+            .unwrap();
+
+        assert!(readme.diff().contains("-old\n+new\n"));
+        assert!(readme.save(true).unwrap());
+        assert!(fs::read_to_string(&path).unwrap().contains("old"));
+
+        assert!(readme.save(false).unwrap());
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            format!("intro\n{STARS}\n### Stars\nnew\n{STARS}\n")
+        );
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+
+        let unchanged = Readme::load(&path).unwrap();
+        assert!(unchanged.diff().is_empty());
+        assert!(!unchanged.save(false).unwrap());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,161 +1,530 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+/// Columns, order and the optional chart of the table are set in the `[readme.benchmarks]` section of `aoc.toml`.
+use std::{
+    cmp,
+    collections::BTreeMap,
+    fmt::Write as _,
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
 
+use serde::Deserialize;
+
+use crate::template::{
+    config, data, readme,
+    runner::{format_bytes, BenchStats},
+};
 use crate::Day;
 
-static MARKER: &str = "<!--- benchmarking table --->";
+pub use crate::template::readme::Error;
+
+/// Default marker surrounding the benchmark table, see `[readme]` in `aoc.toml`.
+pub const MARKER: &str = "<!--- benchmarking table --->";
 
-#[derive(Debug)]
-pub enum Error {
-    Parser(String),
-    IO(io::Error),
+/// A column of the benchmark table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Column {
+    Parse,
+    #[serde(rename = "part_1")]
+    Part1,
+    #[serde(rename = "part_2")]
+    Part2,
+    /// Time of the parse phase and both parts.
+    Total,
+    /// Median time of both parts.
+    Median,
+    /// 95th percentile time of both parts.
+    P95,
+    /// Number of benchmark samples of both parts.
+    Samples,
+    /// Peak memory use of the solution binary.
+    Memory,
+    /// Lines of code of the solution, without tests.
+    Lines,
+    /// Change of the total time since the previous update.
+    Delta,
 }
 
-impl From<std::io::Error> for Error {
-    fn from(e: std::io::Error) -> Self {
-        Error::IO(e)
+impl Column {
+    fn header(self) -> &'static str {
+        match self {
+            Column::Parse => "Parse",
+            Column::Part1 => "Part 1",
+            Column::Part2 => "Part 2",
+            Column::Total => "Total",
+            Column::Median => "Median",
+            Column::P95 => "p95",
+            Column::Samples => "Samples",
+            Column::Memory => "Memory",
+            Column::Lines => "Lines",
+            Column::Delta => "Δ",
+        }
     }
 }
 
-#[derive(Clone)]
+/// Order of the rows of the benchmark table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    #[default]
+    Day,
+    /// Slowest days first.
+    Cost,
+}
+
+/// Unit of the times in the benchmark table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeUnit {
+    /// The unit that fits each time, like `Duration`'s `Debug` output.
+    #[default]
+    Auto,
+    Ns,
+    #[serde(alias = "µs")]
+    Us,
+    Ms,
+    S,
+}
+
+/// Settings of the benchmark table.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TableConfig {
+    pub columns: Vec<Column>,
+    pub sort: SortOrder,
+    /// Unit of all times in the table, so that they can be compared at a glance.
+    pub unit: TimeUnit,
+    /// Decimal places of times.
+    pub precision: usize,
+    /// SVG bar chart of the time per day, written relative to the readme and linked below the table.
+    pub chart: Option<String>,
+    /// Totals of the previous update, compared against by the `delta` column.
+    pub history: PathBuf,
+}
+
+impl Default for TableConfig {
+    fn default() -> Self {
+        TableConfig {
+            columns: vec![Column::Parse, Column::Part1, Column::Part2],
+            sort: SortOrder::Day,
+            unit: TimeUnit::Auto,
+            precision: 1,
+            chart: None,
+            history: PathBuf::from("target/benchmarks.toml"),
+        }
+    }
+}
+
+/// Timings of a day, as measured by its binary.
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Timings {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
-    pub total_nanos: f64,
+    /// Only set for solutions with a separate parse phase.
+    pub parse: Option<BenchStats>,
+    pub part_1: Option<BenchStats>,
+    pub part_2: Option<BenchStats>,
+    /// Peak memory use in bytes.
+    pub memory: Option<u64>,
+    pub lines_of_code: Option<usize>,
 }
 
-pub struct TablePosition {
-    pos_start: usize,
-    pos_end: usize,
+impl Timings {
+    /// Timings of a day without any parts run yet.
+    #[must_use]
+    pub fn new(day: Day) -> Self {
+        Timings {
+            day,
+            parse: None,
+            part_1: None,
+            part_2: None,
+            memory: None,
+            lines_of_code: None,
+        }
+    }
+
+    #[must_use]
+    pub fn part(&self, part: u8) -> Option<&BenchStats> {
+        match part {
+            1 => self.part_1.as_ref(),
+            2 => self.part_2.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Mean time of the parse phase and both parts.
+    #[must_use]
+    pub fn total(&self) -> Duration {
+        [&self.parse, &self.part_1, &self.part_2]
+            .into_iter()
+            .flatten()
+            .map(|stats| stats.mean)
+            .sum()
+    }
 }
 
+/// Total time of all days.
+#[must_use]
+pub fn total(timings: &[Timings]) -> Duration {
+    timings.iter().map(Timings::total).sum()
+}
+
+/// Total time per day of the previous update, in nanoseconds.
+pub type History = BTreeMap<Day, f64>;
+
 #[must_use]
 pub fn get_path_for_bin(day: Day) -> String {
     format!("./src/bin/{day}.rs")
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+/// Lines of code of a solution: lines before its tests, without blank lines and comments.
+#[must_use]
+pub fn count_lines_of_code(source: &str) -> usize {
+    code_lines(source).count()
+}
+
+/// Trimmed lines of a solution that hold code, i.e. without blank lines, comments and tests.
+pub fn code_lines(source: &str) -> impl Iterator<Item = &str> {
+    source
+        .lines()
+        .take_while(|line| !is_test_attribute(line))
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with("//"))
+}
 
-    if matches.len() > 2 {
-        return Err(Error::Parser(
-            "{}: too many occurences of marker in README.".into(),
-        ));
+/// Whether a line is a `#[cfg(...)]` attribute of tests, e.g. `#[cfg(all(test, feature = "fuzz"))]`.
+fn is_test_attribute(line: &str) -> bool {
+    line.trim_start().strip_prefix("#[cfg(").is_some_and(|cfg| {
+        cfg.split(|c: char| !c.is_alphanumeric() && c != '_')
+            .any(|word| word == "test")
+    })
+}
+
+/// Formats a time in the configured unit, e.g. `1.5ms`.
+#[must_use]
+pub fn format_time(duration: Duration, unit: TimeUnit, precision: usize) -> String {
+    let secs = duration.as_secs_f64();
+
+    match unit {
+        TimeUnit::Auto => format!("{duration:.precision$?}"),
+        TimeUnit::Ns => format!("{:.precision$}ns", secs * 1e9),
+        TimeUnit::Us => format!("{:.precision$}µs", secs * 1e6),
+        TimeUnit::Ms => format!("{:.precision$}ms", secs * 1e3),
+        TimeUnit::S => format!("{secs:.precision$}s"),
     }
+}
 
-    let pos_start = matches
-        .first()
-        .map(|m| m.0)
-        .ok_or_else(|| Error::Parser("Could not find table start position.".into()))?;
+/// `a / b` of the values of both parts, `-` for missing parts.
+fn both_parts(timing: &Timings, format: impl Fn(&BenchStats) -> String) -> String {
+    match (timing.part(1), timing.part(2)) {
+        (None, None) => "-".into(),
+        (part_1, part_2) => [part_1, part_2]
+            .map(|stats| stats.map_or_else(|| "-".into(), &format))
+            .join(" / "),
+    }
+}
 
-    let pos_end = matches
-        .last()
-        .map(|m| m.0 + m.1.len())
-        .ok_or_else(|| Error::Parser("Could not find table end position.".into()))?;
+fn construct_cell(
+    column: Column,
+    timing: &Timings,
+    table: &TableConfig,
+    history: &History,
+) -> String {
+    let time = |duration| format!("`{}`", format_time(duration, table.unit, table.precision));
+    let mean =
+        |stats: Option<&BenchStats>| stats.map_or_else(|| "-".into(), |stats| time(stats.mean));
 
-    Ok(TablePosition { pos_start, pos_end })
+    match column {
+        Column::Parse => mean(timing.parse.as_ref()),
+        Column::Part1 => mean(timing.part(1)),
+        Column::Part2 => mean(timing.part(2)),
+        Column::Total => time(timing.total()),
+        Column::Median => both_parts(timing, |stats| time(stats.median)),
+        Column::P95 => both_parts(timing, |stats| time(stats.p95)),
+        Column::Samples => both_parts(timing, |stats| stats.samples.to_string()),
+        Column::Memory => timing.memory.map_or_else(
+            || "-".into(),
+            |memory| format!("`{}`", format_bytes(memory)),
+        ),
+        Column::Lines => timing
+            .lines_of_code
+            .map_or_else(|| "-".into(), |lines| lines.to_string()),
+        Column::Delta => match history.get(&timing.day) {
+            Some(&previous) if previous > 0.0 => {
+                format!(
+                    "{:+.1}%",
+                    (timing.total().as_secs_f64() * 1e9 / previous - 1.0) * 100.0
+                )
+            }
+            _ => "-".into(),
+        },
+    }
+}
+
+fn sort_timings(timings: &mut [Timings], order: SortOrder) {
+    match order {
+        SortOrder::Day => timings.sort_by_key(|timing| timing.day),
+        SortOrder::Cost => timings.sort_by_key(|timing| cmp::Reverse(timing.total())),
+    }
 }
 
-fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> String {
+fn construct_table(
+    prefix: &str,
+    mut timings: Vec<Timings>,
+    table: &TableConfig,
+    history: &History,
+) -> String {
+    let total_millis = total(&timings).as_secs_f64() * 1e3;
     let header = format!("{prefix} Benchmarks");
 
+    let headers: Vec<&str> = table.columns.iter().map(|column| column.header()).collect();
+
     let mut lines: Vec<String> = vec![
-        MARKER.into(),
         header,
         String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
+        format!("| Day | {} |", headers.join(" | ")),
+        format!("| :---: |{}", " :---: |".repeat(headers.len())),
     ];
 
-    for timing in timings {
-        let path = get_path_for_bin(timing.day);
+    sort_timings(&mut timings, table.sort);
+
+    for timing in &timings {
+        let cells: Vec<String> = table
+            .columns
+            .iter()
+            .map(|&column| construct_cell(column, timing, table, history))
+            .collect();
+
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+            "| [Day {}]({}) | {} |",
             timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            get_path_for_bin(timing.day),
+            cells.join(" | ")
         ));
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+
+    if let Some(chart) = &table.chart {
+        lines.push(String::new());
+        lines.push(format!("![Time per day]({chart})"));
+    }
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
-    s.replace_range(positions.pos_start..positions.pos_end, &table);
-    Ok(())
+/// Horizontal bar chart of the time per day, in the order of the table.
+fn construct_chart(mut timings: Vec<Timings>, table: &TableConfig) -> String {
+    const ROW: usize = 24;
+    const LABEL: usize = 70;
+    const BAR: f64 = 400.0;
+
+    sort_timings(&mut timings, table.sort);
+
+    let max_total = timings.iter().map(Timings::total).max().unwrap_or_default();
+    let height = ROW * timings.len() + 8;
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{height}\" font-family=\"monospace\" font-size=\"12\">\n",
+        LABEL + 520
+    );
+
+    for (i, timing) in timings.iter().enumerate() {
+        let y = 4 + i * ROW;
+        let width = if max_total.is_zero() {
+            1.0
+        } else {
+            (timing.total().as_secs_f64() / max_total.as_secs_f64() * BAR).max(1.0)
+        };
+
+        let _ = writeln!(
+            svg,
+            "  <text x=\"0\" y=\"{}\">Day {}</text>",
+            y + 15,
+            timing.day.into_inner()
+        );
+        let _ = writeln!(
+            svg,
+            "  <rect x=\"{LABEL}\" y=\"{}\" width=\"{width:.1}\" height=\"{}\" fill=\"#e8a33d\" />",
+            y + 2,
+            ROW - 6
+        );
+        let _ = writeln!(
+            svg,
+            "  <text x=\"{:.1}\" y=\"{}\">{}</text>",
+            LABEL as f64 + width + 6.0,
+            y + 15,
+            format_time(timing.total(), table.unit, table.precision)
+        );
+    }
+
+    svg.push_str("</svg>\n");
+    svg
 }
 
-pub fn update(timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
-    let path = "README.md";
-    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    update_content(&mut readme, timings, total_millis)?;
-    fs::write(path, &readme)?;
+fn update_content(
+    s: &mut String,
+    timings: Vec<Timings>,
+    marker: &str,
+    table: &TableConfig,
+    history: &History,
+) -> Result<(), Error> {
+    let prefix = readme::heading_prefix(s, marker)?;
+    let table = construct_table(&prefix, timings, table, history);
+    readme::replace_section(s, marker, &table)
+}
+
+fn load_history(path: &Path) -> Result<History, Error> {
+    let s = match fs::read_to_string(path) {
+        Ok(s) => s,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(History::new()),
+        Err(e) => return Err(e.into()),
+    };
+
+    let totals: BTreeMap<String, f64> =
+        toml::from_str(&s).map_err(|e| Error::Parser(format!("Invalid benchmark history: {e}")))?;
+
+    Ok(totals
+        .into_iter()
+        .filter_map(|(day, nanos)| Some((day.parse().ok()?, nanos)))
+        .collect())
+}
+
+fn save_history(path: &Path, timings: &[Timings]) -> Result<(), Error> {
+    let mut s = String::from("# Total nanoseconds per day of the last benchmark update.\n");
+    for timing in timings {
+        let _ = writeln!(
+            s,
+            "{} = {}",
+            timing.day.into_inner(),
+            timing.total().as_nanos()
+        );
+    }
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, s)?;
     Ok(())
 }
 
+/// Rewrites the benchmark table of the readme, or prints the changes as a diff if `dry_run` is set.
+pub fn update(timings: Vec<Timings>, dry_run: bool) -> Result<(), Error> {
+    let readme_config = &config::get().readme;
+    let table = &readme_config.benchmarks;
+    let history_path = data::project_path(&table.history);
+    let history = load_history(&history_path)?;
+
+    let readme_path = data::project_path(&readme_config.path);
+    let mut readme = readme::Readme::load(&readme_path)?;
+    update_content(
+        readme.content_mut(),
+        timings.clone(),
+        &readme_config.benchmarks_marker,
+        table,
+        &history,
+    )?;
+    readme.save(dry_run)?;
+
+    if dry_run {
+        return Ok(());
+    }
+
+    if let Some(chart) = &table.chart {
+        let chart_path = readme_path
+            .parent()
+            .unwrap_or_else(|| Path::new(""))
+            .join(chart);
+        fs::write(chart_path, construct_chart(timings.clone(), table))?;
+    }
+
+    save_history(&history_path, &timings)
+}
+
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, Timings, MARKER};
+    use super::{
+        construct_chart, count_lines_of_code, format_time, update_content, Column, History,
+        SortOrder, TableConfig, TimeUnit, Timings, MARKER,
+    };
     use crate::day;
+    use crate::template::runner::BenchStats;
+    use std::time::Duration;
+
+    fn ms(millis: u64) -> Option<BenchStats> {
+        Some(BenchStats::from_samples(vec![Duration::from_millis(
+            millis,
+        )]))
+    }
 
     fn get_mock_timings() -> Vec<Timings> {
         vec![
             Timings {
-                day: day!(1),
-                part_1: Some("10ms".into()),
-                part_2: Some("20ms".into()),
-                total_nanos: 3e+10,
+                part_1: ms(10),
+                part_2: ms(20),
+                ..Timings::new(day!(1))
             },
             Timings {
-                day: day!(2),
-                part_1: Some("30ms".into()),
-                part_2: Some("40ms".into()),
-                total_nanos: 7e+10,
+                parse: ms(5),
+                part_1: Some(BenchStats {
+                    mean: Duration::from_millis(30),
+                    median: Duration::from_millis(29),
+                    p95: Duration::from_millis(35),
+                    samples: 34,
+                }),
+                part_2: ms(40),
+                memory: Some(2 * 1024 * 1024),
+                lines_of_code: Some(42),
+                ..Timings::new(day!(2))
             },
             Timings {
-                day: day!(4),
-                part_1: Some("40ms".into()),
-                part_2: Some("50ms".into()),
-                total_nanos: 9e+10,
+                part_1: ms(40),
+                part_2: ms(50),
+                ..Timings::new(day!(4))
             },
         ]
     }
 
+    fn update_default(s: &mut String) {
+        update_content(
+            s,
+            get_mock_timings(),
+            MARKER,
+            &TableConfig::default(),
+            &History::new(),
+        )
+        .unwrap();
+    }
+
     #[test]
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_default(&mut s);
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_default(&mut s);
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_default(&mut s);
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_default(&mut s);
+        update_default(&mut s);
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -163,24 +532,148 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_default(&mut s);
         let expected = [
             "foo",
             "bar",
             "<!--- benchmarking table --->",
             "## Benchmarks",
             "",
-            "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| Day | Parse | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: | :---: |",
+            "| [Day 1](./src/bin/01.rs) | - | `10.0ms` | `20.0ms` |",
+            "| [Day 2](./src/bin/02.rs) | `5.0ms` | `30.0ms` | `40.0ms` |",
+            "| [Day 4](./src/bin/04.rs) | - | `40.0ms` | `50.0ms` |",
             "",
-            "**Total: 190.00ms**",
+            "**Total: 195.00ms**",
             "<!--- benchmarking table --->",
             "baz",
         ]
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_configured_columns() {
+        let table = TableConfig {
+            columns: vec![
+                Column::Total,
+                Column::Median,
+                Column::P95,
+                Column::Samples,
+                Column::Memory,
+                Column::Lines,
+                Column::Delta,
+            ],
+            sort: SortOrder::Cost,
+            chart: Some("./benchmarks.svg".into()),
+            ..TableConfig::default()
+        };
+        let history = History::from([(day!(2), 3.75e+7), (day!(4), 9e+7)]);
+
+        let mut s = format!("{}\n### Benchmarks\n{}", MARKER, MARKER);
+        update_content(&mut s, get_mock_timings(), MARKER, &table, &history).unwrap();
+        let expected = [
+            "<!--- benchmarking table --->",
+            "### Benchmarks",
+            "",
+            "| Day | Total | Median | p95 | Samples | Memory | Lines | Δ |",
+            "| :---: | :---: | :---: | :---: | :---: | :---: | :---: | :---: |",
+            "| [Day 4](./src/bin/04.rs) | `90.0ms` | `40.0ms` / `50.0ms` | `40.0ms` / `50.0ms` | 1 / 1 | - | - | +0.0% |",
+            "| [Day 2](./src/bin/02.rs) | `75.0ms` | `29.0ms` / `40.0ms` | `35.0ms` / `40.0ms` | 34 / 1 | `2.0 MiB` | 42 | +100.0% |",
+            "| [Day 1](./src/bin/01.rs) | `30.0ms` | `10.0ms` / `20.0ms` | `10.0ms` / `20.0ms` | 1 / 1 | - | - | - |",
+            "",
+            "**Total: 195.00ms**",
+            "",
+            "![Time per day](./benchmarks.svg)",
+            "<!--- benchmarking table --->",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn formats_times_in_units() {
+        let time = Duration::from_micros(1500);
+
+        assert_eq!(format_time(time, TimeUnit::Auto, 1), "1.5ms");
+        assert_eq!(format_time(time, TimeUnit::Ns, 0), "1500000ns");
+        assert_eq!(format_time(time, TimeUnit::Us, 1), "1500.0µs");
+        assert_eq!(format_time(time, TimeUnit::Ms, 3), "1.500ms");
+        assert_eq!(format_time(time, TimeUnit::S, 4), "0.0015s");
+        assert_eq!(
+            format_time(Duration::from_nanos(74), TimeUnit::Auto, 2),
+            "74.00ns"
+        );
+    }
+
+    #[test]
+    fn unifies_units() {
+        let table = TableConfig {
+            unit: TimeUnit::Ms,
+            precision: 2,
+            ..TableConfig::default()
+        };
+        let timings = vec![Timings {
+            part_1: Some(BenchStats::from_samples(vec![Duration::from_nanos(74)])),
+            part_2: ms(1500),
+            ..Timings::new(day!(1))
+        }];
+
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, timings, MARKER, &table, &History::new()).unwrap();
+        assert!(s.contains("| - | `0.00ms` | `1500.00ms` |"));
+    }
+
+    #[test]
+    fn draws_chart() {
+        let chart = construct_chart(get_mock_timings(), &TableConfig::default());
+
+        assert!(chart.starts_with("<svg"));
+        assert_eq!(chart.matches("<rect").count(), 3);
+        // the slowest day spans the full width.
+        assert!(chart.contains("width=\"400.0\""));
+        assert!(chart.contains(">Day 4</text>"));
+        assert!(chart.contains(">90.0ms</text>"));
+    }
+
+    #[test]
+    fn counts_lines_of_code() {
+        let source = [
+            "advent_of_code::solution!(1);",
+            "",
+            "// a comment",
+            "pub fn part_one(input: &str) -> Option<u32> {",
+            "    None",
+            "}",
+            "",
+            "#[cfg(test)]",
+            "mod tests {}",
+        ]
+        .join("\n");
+
+        assert_eq!(count_lines_of_code(&source), 4);
+    }
+
+    #[test]
+    fn stops_at_any_test_module() {
+        let source = [
+            "pub fn part_one(input: &str) -> Option<u32> {",
+            "    None",
+            "}",
+            "#[cfg(feature = \"test_lib\")]",
+            "fn helper() {}",
+            "",
+            "#[cfg(all(test, feature = \"fuzz\"))]",
+            "mod fuzzing {",
+            "    fn generate() {}",
+            "}",
+            "",
+            "#[cfg(test)]",
+            "mod tests {}",
+        ]
+        .join("\n");
+
+        assert_eq!(count_lines_of_code(&source), 5);
+    }
 }
@@ -0,0 +1,144 @@
+/// Module that writes the language statistics of the solutions to the readme: the lines of Rust of each day and the
+/// tracked crates it uses, see [`stats::TRACKED_CRATES`].
+use std::fs;
+
+use crate::template::{
+    data,
+    readme::{self, Error},
+    readme_benchmarks::{self, get_path_for_bin},
+    stats::{self, TRACKED_CRATES},
+};
+use crate::{all_days, Day};
+
+/// Default marker surrounding the language statistics. See `[readme]` in `aoc.toml`.
+pub const MARKER: &str = "<!--- language stats --->";
+
+/// The code of the solution of a day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayCode {
+    pub day: Day,
+    /// Lines of code, without blank lines, comments and tests.
+    pub lines: usize,
+    pub crates: Vec<&'static str>,
+}
+
+impl DayCode {
+    #[must_use]
+    pub fn new(day: Day, source: &str) -> Self {
+        DayCode {
+            day,
+            lines: readme_benchmarks::count_lines_of_code(source),
+            crates: stats::find_crates(source),
+        }
+    }
+}
+
+/// The code of every day with a solution.
+#[must_use]
+pub fn load() -> Vec<DayCode> {
+    all_days()
+        .filter_map(|day| {
+            let source = fs::read_to_string(data::bin_path(day)).ok()?;
+            Some(DayCode::new(day, &source))
+        })
+        .collect()
+}
+
+fn construct_table(prefix: &str, days: &[DayCode]) -> String {
+    let mut lines = vec![
+        format!("{prefix} Language stats"),
+        String::new(),
+        "| Day | Lines of Rust | Crates |".into(),
+        "| :---: | :---: | :---: |".into(),
+    ];
+
+    for code in days {
+        let crates = if code.crates.is_empty() {
+            "-".to_string()
+        } else {
+            code.crates.join(", ")
+        };
+
+        lines.push(format!(
+            "| [Day {}]({}) | {} | {crates} |",
+            code.day.into_inner(),
+            get_path_for_bin(code.day),
+            code.lines
+        ));
+    }
+
+    let total: usize = days.iter().map(|code| code.lines).sum();
+    let usage: Vec<String> = TRACKED_CRATES
+        .iter()
+        .filter_map(|krate| {
+            let count = days
+                .iter()
+                .filter(|code| code.crates.contains(krate))
+                .count();
+            let unit = if count == 1 { "day" } else { "days" };
+            (count > 0).then(|| format!("`{krate}` in {count} {unit}"))
+        })
+        .collect();
+
+    lines.push(String::new());
+    lines.push(format!(
+        "**Total: {total} lines of Rust in {} solutions**",
+        days.len()
+    ));
+
+    if !usage.is_empty() {
+        lines.push(String::new());
+        lines.push(format!("Crates: {}.", usage.join(", ")));
+    }
+
+    lines.join("\n")
+}
+
+pub fn update_content(s: &mut String, days: &[DayCode], marker: &str) -> Result<(), Error> {
+    let prefix = readme::heading_prefix(s, marker)?;
+    let table = construct_table(&prefix, days);
+    readme::replace_section(s, marker, &table)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{update_content, DayCode, MARKER};
+    use crate::day;
+
+    #[test]
+    fn counts_code_of_days() {
+        let code = DayCode::new(
+            day!(8),
+            "use rayon::prelude::*;\n\n// comment\nfn main() {}\n#[cfg(test)]\nmod tests {}\n",
+        );
+
+        assert_eq!(code.lines, 2);
+        assert_eq!(code.crates, ["rayon"]);
+    }
+
+    #[test]
+    fn writes_table() {
+        let mut s = format!("intro\n{MARKER}\n### Language stats\nold\n{MARKER}\n");
+        let days = [
+            DayCode {
+                day: day!(1),
+                lines: 40,
+                crates: vec![],
+            },
+            DayCode {
+                day: day!(8),
+                lines: 120,
+                crates: vec!["rayon", "regex"],
+            },
+        ];
+
+        update_content(&mut s, &days, MARKER).unwrap();
+
+        assert_eq!(
+            s,
+            format!(
+                "intro\n{MARKER}\n### Language stats\n\n| Day | Lines of Rust | Crates |\n| :---: | :---: | :---: |\n| [Day 1](./src/bin/01.rs) | 40 | - |\n| [Day 8](./src/bin/08.rs) | 120 | rayon, regex |\n\n**Total: 160 lines of Rust in 2 solutions**\n\nCrates: `rayon` in 1 day, `regex` in 1 day.\n{MARKER}\n"
+            )
+        );
+    }
+}
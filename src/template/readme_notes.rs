@@ -0,0 +1,92 @@
+/// Module that copies the notes of each day into the readme, e.g. `data/notes/05.md` between two
+/// `<!--- day 05 notes --->` markers. Notes are written in markdown and copied as they are.
+use std::path::Path;
+
+use crate::template::{
+    config,
+    data::{self, DataError},
+    readme::{self, Error},
+};
+use crate::{all_days, Day};
+
+/// Marker surrounding the notes of a day.
+#[must_use]
+pub fn marker(day: Day) -> String {
+    readme::marker(&format!("day {day} notes"))
+}
+
+/// Notes of every day with a file `NN.md` in `dir`.
+pub fn load(dir: &Path) -> Result<Vec<(Day, String)>, Error> {
+    all_days()
+        .filter_map(|day| match data::read(&dir.join(format!("{day}.md"))) {
+            Ok(notes) => Some(Ok((day, notes))),
+            Err(DataError::Missing(_)) => None,
+            Err(e) => Some(Err(Error::Parser(e.to_string()))),
+        })
+        .collect()
+}
+
+/// Replaces the sections of the notes, returning the days whose markers are not in the readme.
+pub fn update_content(s: &mut String, notes: &[(Day, String)]) -> Result<Vec<Day>, Error> {
+    let mut missing = vec![];
+
+    for (day, text) in notes {
+        let marker = marker(*day);
+
+        if s.contains(&marker) {
+            readme::replace_section(s, &marker, text)?;
+        } else {
+            missing.push(*day);
+        }
+    }
+
+    Ok(missing)
+}
+
+/// Copies the notes of the configured directory into the readme. Returns the days without markers.
+pub fn update(readme: &mut readme::Readme) -> Result<Vec<Day>, Error> {
+    let notes = load(&data::project_path(&config::get().readme.notes))?;
+    update_content(readme.content_mut(), &notes)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{load, marker, update_content};
+    use crate::day;
+    use std::fs;
+
+    #[test]
+    fn replaces_sections_of_days() {
+        let five = marker(day!(5));
+        let mut s = format!("# Notes\n{five}\nold\n{five}\nrest\n");
+
+        let missing = update_content(
+            &mut s,
+            &[
+                (day!(5), "### Day 5\nRanges split in two.".into()),
+                (day!(7), "Jokers.".into()),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(five, "<!--- day 05 notes --->");
+        assert_eq!(
+            s,
+            format!("# Notes\n{five}\n### Day 5\nRanges split in two.\n{five}\nrest\n")
+        );
+        assert_eq!(missing, [day!(7)]);
+    }
+
+    #[test]
+    fn loads_notes_files() {
+        let dir = std::env::temp_dir().join(format!("aoc-notes-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("03.md"), "Gears.\r\n").unwrap();
+        fs::write(dir.join("readme.md"), "not a day").unwrap();
+
+        assert_eq!(load(&dir).unwrap(), [(day!(3), "Gears.".to_string())]);
+        assert_eq!(load(&dir.join("missing")).unwrap(), []);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
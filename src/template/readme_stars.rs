@@ -0,0 +1,238 @@
+/// Module that updates the readme with the stars earned so far.
+/// It replaces the `advent-readme-stars` workflow offline: accepted parts are recorded in a progress file instead of
+/// being fetched from a private leaderboard.
+use std::{collections::BTreeMap, fs, io, path::Path};
+
+use serde::Deserialize;
+
+use crate::template::{
+    aoc_cli, config, data,
+    readme::{self, Error},
+};
+use crate::Day;
+
+/// Default marker surrounding the stars table, the one of `advent-readme-stars`. See `[readme]` in `aoc.toml`.
+pub const MARKER: &str = "<!--- advent_readme_stars table --->";
+
+/// Parts accepted by Advent of Code, per day.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Progress {
+    accepted: BTreeMap<Day, [bool; 2]>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ProgressFile {
+    accepted: BTreeMap<String, Vec<u8>>,
+}
+
+impl Progress {
+    /// Parses the contents of a progress file, e.g. `[accepted]` followed by `1 = [1, 2]`.
+    pub fn parse(s: &str) -> Result<Self, Error> {
+        let file: ProgressFile =
+            toml::from_str(s).map_err(|e| Error::Parser(format!("Invalid progress file: {e}")))?;
+
+        let mut progress = Progress::default();
+
+        for (day, parts) in file.accepted {
+            let day: Day = day
+                .parse()
+                .map_err(|_| Error::Parser(format!("Invalid day in progress file: {day}")))?;
+
+            for part in parts {
+                if !(1..=2).contains(&part) {
+                    return Err(Error::Parser(format!(
+                        "Invalid part of day {day} in progress file: {part}"
+                    )));
+                }
+                progress.accept(day, part);
+            }
+        }
+
+        Ok(progress)
+    }
+
+    /// Loads the progress file at `path`. A missing file means no stars yet.
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        match fs::read_to_string(path) {
+            Ok(s) => Progress::parse(&s),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Progress::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.to_toml())?;
+        Ok(())
+    }
+
+    /// Records that a part of a day was accepted.
+    pub fn accept(&mut self, day: Day, part: u8) {
+        self.accepted.entry(day).or_default()[usize::from(part - 1)] = true;
+    }
+
+    #[must_use]
+    pub fn is_accepted(&self, day: Day, part: u8) -> bool {
+        self.accepted
+            .get(&day)
+            .is_some_and(|parts| parts[usize::from(part - 1)])
+    }
+
+    #[must_use]
+    pub fn stars(&self) -> usize {
+        self.accepted
+            .values()
+            .flatten()
+            .filter(|&&part| part)
+            .count()
+    }
+
+    fn to_toml(&self) -> String {
+        let mut lines: Vec<String> = vec![
+            "# Parts accepted by Advent of Code, per day. Updated by `cargo stars <day> <part>`."
+                .into(),
+            "[accepted]".into(),
+        ];
+
+        for (day, parts) in &self.accepted {
+            let parts: Vec<String> = (1..=2)
+                .filter(|part| parts[usize::from(part - 1)])
+                .map(|part: u8| part.to_string())
+                .collect();
+            lines.push(format!("{} = [{}]", day.into_inner(), parts.join(", ")));
+        }
+
+        lines.push(String::new());
+        lines.join("\n")
+    }
+}
+
+fn construct_table(prefix: &str, year: u16, progress: &Progress) -> String {
+    let header = format!("{prefix} {year} Results");
+
+    let mut lines: Vec<String> = vec![
+        header,
+        String::new(),
+        "| Day | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: |".into(),
+    ];
+
+    // days without stars before the last solved day are listed too, like on the calendar.
+    let last_day = progress
+        .accepted
+        .keys()
+        .last()
+        .map_or(0, |day| day.into_inner());
+
+    for day in (1..=last_day).filter_map(Day::new) {
+        let star = |part| {
+            if progress.is_accepted(day, part) {
+                "⭐"
+            } else {
+                " "
+            }
+        };
+        lines.push(format!(
+            "| [Day {}](https://adventofcode.com/{year}/day/{}) | {} | {} |",
+            day.into_inner(),
+            day.into_inner(),
+            star(1),
+            star(2)
+        ));
+    }
+
+    lines.join("\n")
+}
+
+fn update_content(
+    s: &mut String,
+    year: u16,
+    progress: &Progress,
+    marker: &str,
+) -> Result<(), Error> {
+    let prefix = readme::heading_prefix(s, marker)?;
+    let table = construct_table(&prefix, year, progress);
+    readme::replace_section(s, marker, &table)
+}
+
+/// Rewrites the stars table of the readme, or prints the changes as a diff if `dry_run` is set.
+pub fn update(progress: &Progress, dry_run: bool) -> Result<(), Error> {
+    let config = config::get();
+    let year = aoc_cli::get_year()
+        .ok_or_else(|| Error::Parser("No year configured for the stars table.".into()))?;
+
+    let mut readme = readme::Readme::load(&data::project_path(&config.readme.path))?;
+    update_content(
+        readme.content_mut(),
+        year,
+        progress,
+        &config.readme.stars_marker,
+    )?;
+    readme.save(dry_run)?;
+    Ok(())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{update_content, Progress, MARKER};
+    use crate::day;
+
+    fn get_mock_progress() -> Progress {
+        Progress::parse("[accepted]\n1 = [1, 2]\n3 = [1]\n").unwrap()
+    }
+
+    #[test]
+    fn parses_progress() {
+        let progress = get_mock_progress();
+
+        assert!(progress.is_accepted(day!(1), 2));
+        assert!(progress.is_accepted(day!(3), 1));
+        assert!(!progress.is_accepted(day!(3), 2));
+        assert!(!progress.is_accepted(day!(2), 1));
+        assert_eq!(progress.stars(), 3);
+
+        assert_eq!(Progress::parse("").unwrap(), Progress::default());
+        assert!(Progress::parse("[accepted]\n26 = [1]").is_err());
+        assert!(Progress::parse("[accepted]\n1 = [3]").is_err());
+    }
+
+    #[test]
+    fn round_trips_progress() {
+        let mut progress = get_mock_progress();
+        progress.accept(day!(12), 1);
+
+        assert_eq!(Progress::parse(&progress.to_toml()).unwrap(), progress);
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_if_marker_not_present() {
+        let mut s = "# readme".to_string();
+        update_content(&mut s, 2023, &get_mock_progress(), MARKER).unwrap();
+    }
+
+    #[test]
+    fn format_stars() {
+        let mut s = format!("foo\n{}\n## 2023 Results\n{}\nbar", MARKER, MARKER);
+        update_content(&mut s, 2023, &get_mock_progress(), MARKER).unwrap();
+        update_content(&mut s, 2023, &get_mock_progress(), MARKER).unwrap();
+        let expected = [
+            "foo",
+            "<!--- advent_readme_stars table --->",
+            "## 2023 Results",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1](https://adventofcode.com/2023/day/1) | ⭐ | ⭐ |",
+            "| [Day 2](https://adventofcode.com/2023/day/2) |   |   |",
+            "| [Day 3](https://adventofcode.com/2023/day/3) | ⭐ |   |",
+            "<!--- advent_readme_stars table --->",
+            "bar",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+}
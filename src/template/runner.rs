@@ -1,63 +1,382 @@
 /// Encapsulates code that interacts with solution functions.
+use crate::template::answer::Answer;
+use crate::template::commands::download;
+use crate::template::config::{self, SubmitBackend};
+use crate::template::data::{self, DataError, DataStore};
+use crate::template::logging::{self, PARSE_PHASE};
+use crate::template::memo::{self, MemoStats};
+use crate::template::params::{self, ParamInfo};
+use crate::template::progress;
+use crate::template::viz::{self, Recorder, VizMode, VizOptions};
 use crate::template::{aoc_cli, ANSI_ITALIC, ANSI_RESET};
 use crate::Day;
-use std::fmt::Display;
-use std::io::{stdout, Write};
+use clap::{Parser, ValueEnum};
+use serde::{Deserialize, Serialize};
+use std::convert::Infallible;
+use std::fmt::{self, Display};
+use std::io::{stderr, stdin, stdout, IsTerminal, Write};
+use std::path::PathBuf;
 use std::process::Output;
+use std::str::FromStr;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, fs, process};
 
 use super::ANSI_BOLD;
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// Where a day binary reads its puzzle input from.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum InputSource {
+    /// `NN.txt` in the inputs directory.
+    #[default]
+    Inputs,
+    /// `NN.txt` in the examples directory.
+    Examples,
+    /// Any other file.
+    File(PathBuf),
+}
+
+impl FromStr for InputSource {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "inputs" => InputSource::Inputs,
+            "examples" => InputSource::Examples,
+            path => InputSource::File(PathBuf::from(path)),
+        })
+    }
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputSource::Inputs => write!(f, "inputs"),
+            InputSource::Examples => write!(f, "examples"),
+            InputSource::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// Human-readable results.
+    #[default]
+    Text,
+    /// One JSON object per part, for scripts.
+    Json,
+}
+
+impl Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OutputFormat::Text => write!(f, "text"),
+            OutputFormat::Json => write!(f, "json"),
+        }
+    }
+}
+
+/// Options of a solution run. Parsed once by the `main` that `solution!` generates, and shared with the
+/// `solve` command so both accept the same flags. Unset options fall back to `aoc.toml`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Parser)]
+pub struct RunOptions {
+    /// Benchmark the solution and print the average execution time.
+    #[arg(long)]
+    pub time: bool,
+    /// Submit the answer of a part via aoc-cli.
+    #[arg(long, value_name = "PART", value_parser = clap::value_parser!(u8).range(1..=2))]
+    pub submit: Option<u8>,
+    /// Time spent benchmarking each part with `--time`, in milliseconds. [default: 1000]
+    #[arg(long, value_name = "MS")]
+    pub bench_budget: Option<u64>,
+    /// Input to solve: `inputs`, `examples` or the path to a file.
+    #[arg(long, value_name = "SOURCE", default_value_t)]
+    pub input: InputSource,
+    /// Format of the results printed to stdout. [default: text]
+    #[arg(long, value_enum)]
+    pub format: Option<OutputFormat>,
+    /// Show `tracing` output of the solution on stderr, e.g. `debug` or `11=trace`. Overrides `RUST_LOG`.
+    #[arg(long, value_name = "FILTER")]
+    pub log: Option<String>,
+    /// Record the frames that the solution emits through `template::viz`.
+    #[arg(long, value_enum, value_name = "MODE")]
+    pub viz: Option<VizMode>,
+    /// Frames per second of `--viz terminal`, 0 for no delay. [default: 10]
+    #[arg(long, value_name = "FPS", requires = "viz")]
+    pub viz_fps: Option<u32>,
+    /// Number of frames recorded per part before further frames are dropped. [default: 500]
+    #[arg(long, value_name = "N", requires = "viz")]
+    pub viz_max_frames: Option<usize>,
+    /// Override a parameter of the solution, e.g. `expansion=100`. Can be repeated.
+    #[arg(long = "param", value_name = "NAME=VALUE", value_parser = parse_param)]
+    pub params: Vec<(String, String)>,
+}
+
+fn parse_param(s: &str) -> Result<(String, String), String> {
+    s.split_once('=')
+        .filter(|(name, _)| !name.is_empty())
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .ok_or_else(|| format!("expected NAME=VALUE, got {s}"))
+}
+
+impl RunOptions {
+    /// Parses the options from the arguments of the current process, exiting with a usage message on error.
+    #[must_use]
+    pub fn from_env() -> Self {
+        RunOptions::parse()
+    }
+
+    /// The options as command-line arguments, used to forward them to a day binary. Defaults are omitted.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![];
+
+        if let Some(part) = self.submit {
+            args.extend(["--submit".into(), part.to_string()]);
+        }
+        if self.time {
+            args.push("--time".into());
+        }
+        if let Some(budget) = self.bench_budget {
+            args.extend(["--bench-budget".into(), budget.to_string()]);
+        }
+        if self.input != InputSource::default() {
+            args.extend(["--input".into(), self.input.to_string()]);
+        }
+        if let Some(format) = self.format {
+            args.extend(["--format".into(), format.to_string()]);
+        }
+        if let Some(filter) = &self.log {
+            args.extend(["--log".into(), filter.clone()]);
+        }
+        if let Some(mode) = self.viz {
+            args.extend(["--viz".into(), mode.to_string()]);
+        }
+        if let Some(fps) = self.viz_fps {
+            args.extend(["--viz-fps".into(), fps.to_string()]);
+        }
+        if let Some(max_frames) = self.viz_max_frames {
+            args.extend(["--viz-max-frames".into(), max_frames.to_string()]);
+        }
+        for (name, value) in &self.params {
+            args.extend(["--param".into(), format!("{name}={value}")]);
+        }
+
+        args
+    }
+
+    /// Benchmark budget from `--bench-budget` or `aoc.toml`.
+    #[must_use]
+    pub fn bench_budget(&self) -> Duration {
+        Duration::from_millis(
+            self.bench_budget
+                .unwrap_or_else(|| config::get().bench.budget_ms),
+        )
+    }
+
+    /// Output format from `--format` or `aoc.toml`.
+    #[must_use]
+    pub fn format(&self) -> OutputFormat {
+        self.format.unwrap_or_else(|| config::get().output.format)
+    }
+
+    /// Progress bars are only drawn for interactive runs: not with `--time`, JSON output or a redirected stderr.
+    #[must_use]
+    pub fn shows_progress(&self) -> bool {
+        !self.time && self.format() == OutputFormat::Text && stderr().is_terminal()
+    }
+
+    /// Visualisation settings, if `--viz` is set.
+    #[must_use]
+    pub fn viz_options(&self) -> Option<VizOptions> {
+        let defaults = VizOptions::default();
+
+        self.viz.map(|mode| VizOptions {
+            mode,
+            fps: self.viz_fps.unwrap_or(defaults.fps),
+            max_frames: self.viz_max_frames.unwrap_or(defaults.max_frames),
+            ..defaults
+        })
+    }
+
+    /// Reads the puzzle input of `day` from the selected source. Exits with the path of a file that cannot be read,
+    /// after offering to download a missing input when run in a terminal.
+    #[must_use]
+    pub fn read_input(&self, day: Day) -> String {
+        let input = match &self.input {
+            InputSource::Inputs => DataStore::locate().and_then(|store| store.input(day)),
+            InputSource::Examples => DataStore::locate().and_then(|store| store.example(day)),
+            InputSource::File(path) => data::read(path),
+        };
+
+        input.unwrap_or_else(|e| {
+            eprintln!("{e}");
+
+            if matches!(e, DataError::MissingInput(..)) && confirm("Download it now?") {
+                download::handle(day);
+                return self.read_input(day);
+            }
+
+            process::exit(1);
+        })
+    }
+}
+
+/// Asks a yes or no question on the terminal, `false` if stdin or stderr is not a terminal.
+fn confirm(question: &str) -> bool {
+    if !stdin().is_terminal() || !stderr().is_terminal() {
+        return false;
+    }
+
+    eprint!("{question} [y/N] ");
+    let _ = stderr().flush();
+
+    let mut answer = String::new();
+    stdin().read_line(&mut answer).is_ok() && answer.trim().eq_ignore_ascii_case("y")
+}
+
+/// Applies `--param` to the parameters `declared` by the day, exiting with the list of parameters on error.
+pub fn init_params(options: &RunOptions, declared: &[ParamInfo]) {
+    if let Err(e) = params::init(&options.params, declared) {
+        eprintln!("{e}");
+        process::exit(1);
+    }
+}
+
+/// Runs the parse phase of a two-phase solution and reports its time once, before the parts.
+/// Like parts, the parser is benched with `--time`.
+pub fn run_parse<T>(func: impl Fn(&str) -> T, input: &str, day: Day, options: &RunOptions) -> T {
+    let format = options.format();
+    let _guard = logging::enter_part(day, PARSE_PHASE);
+    progress::set_visible(options.shows_progress());
+
+    let (parsed, stats, _) = run_timed(func, input, options, |_| {
+        progress::clear();
+
+        if format == OutputFormat::Text {
+            print!("Parse:");
+        }
+    });
+
+    match format {
+        OutputFormat::Text => {
+            print!("\r");
+            println!("Parse:{}", format_duration(&stats.mean, stats.samples));
+            print_bench_stats(&stats);
+        }
+        OutputFormat::Json => println!("{}", format_parse_json(&stats)),
+    }
+
+    parsed
+}
+
+pub fn run_part<I: Clone, T: Answer>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    day: Day,
+    part: u8,
+    options: &RunOptions,
+) {
     let part_str = format!("Part {part}");
+    let format = options.format();
+    let is_text = format == OutputFormat::Text;
+    let _guard = logging::enter_part(day, part);
+    progress::set_visible(options.shows_progress());
+
+    if let Some(viz_options) = options.viz_options() {
+        viz::begin(day, part, viz_options);
+    }
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (result, stats, memo_stats) = run_timed(func, input, options, |result| {
+        progress::clear();
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+        // only the first run is visualised, benchmarks never record frames.
+        if let Some(recorder) = viz::end() {
+            print_viz_summary(&recorder);
+        }
+
+        if is_text {
+            print_result(result, &part_str, "");
+        }
+    });
+
+    match format {
+        OutputFormat::Text => {
+            print_result(
+                &result,
+                &part_str,
+                &format_duration(&stats.mean, stats.samples),
+            );
+            print_bench_stats(&stats);
+            print_memo_stats(&memo_stats);
+        }
+        OutputFormat::Json => {
+            println!(
+                "{}",
+                format_json(part, result.as_ref(), &stats, &memo_stats)
+            );
+        }
+    }
 
     if let Some(result) = result {
-        submit_result(result, day, part);
+        submit_result(result, day, part, options);
     }
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///  2. with `--time`, the function is benched (approx. the bench budget or 10 samples, whatever take longer.)
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
+    options: &RunOptions,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, BenchStats, MemoStats) {
+    // discard statistics of caches that were dropped before this part started.
+    memo::take_stats();
+
     let timer = Instant::now();
     let result = func(input.clone());
     let base_time = timer.elapsed();
 
+    // caches are scoped to a single call, so these are the statistics of the first (cold) run.
+    let memo_stats = memo::take_stats();
+
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    let stats = if options.time {
+        // benchmarks would repeat every log line of the first run.
+        let run = logging::muted(|| {
+            bench(
+                func,
+                input,
+                &base_time,
+                &options.bench_budget(),
+                options.format() == OutputFormat::Text,
+            )
+        });
+        memo::take_stats();
+        run
     } else {
-        (base_time, 1)
+        BenchStats::from_samples(vec![base_time])
     };
 
-    (result, run.0, run.1)
+    (result, stats, memo_stats)
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+fn bench<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    budget: &Duration,
+    show_status: bool,
+) -> BenchStats {
+    if show_status {
+        print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+        let _ = stdout().flush();
+    }
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
-
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
+    let bench_iterations = bench_iterations(base_time, budget);
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -69,22 +388,49 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    BenchStats::from_samples(timers)
+}
+
+/// Number of samples that fit in the budget, between 10 and 10000.
+fn bench_iterations(base_time: &Duration, budget: &Duration) -> u128 {
+    (budget.as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000)
+}
+
+/// Timing statistics of the runs of a part.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchStats {
+    pub mean: Duration,
+    pub median: Duration,
+    /// 95th percentile, the time most runs stay below.
+    pub p95: Duration,
+    pub samples: u128,
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+impl BenchStats {
+    /// Statistics of a non-empty list of run times.
+    /// # Panics
+    /// If `timers` is empty.
+    #[must_use]
+    pub fn from_samples(mut timers: Vec<Duration>) -> Self {
+        timers.sort_unstable();
+
+        let samples = timers.len() as u128;
+        // nearest-rank percentiles.
+        let percentile = |p: usize| timers[(timers.len() * p).div_ceil(100).max(1) - 1];
+
+        BenchStats {
+            #[allow(clippy::cast_possible_truncation)]
+            mean: Duration::from_nanos(
+                (timers.iter().map(Duration::as_nanos).sum::<u128>() / samples) as u64,
+            ),
+            median: percentile(50),
+            p95: percentile(95),
+            samples,
+        }
+    }
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
+pub(crate) fn format_duration(duration: &Duration, samples: u128) -> String {
     if samples == 1 {
         format!(" ({duration:.1?})")
     } else {
@@ -92,7 +438,208 @@ fn format_duration(duration: &Duration, samples: u128) -> String {
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+/// Timings of a JSON record, in exact nanoseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JsonTimings {
+    pub nanos: u64,
+    pub samples: u64,
+    pub median_nanos: u64,
+    pub p95_nanos: u64,
+}
+
+impl From<&BenchStats> for JsonTimings {
+    fn from(stats: &BenchStats) -> Self {
+        let nanos = |duration: Duration| u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX);
+
+        JsonTimings {
+            nanos: nanos(stats.mean),
+            samples: u64::try_from(stats.samples).unwrap_or(u64::MAX),
+            median_nanos: nanos(stats.median),
+            p95_nanos: nanos(stats.p95),
+        }
+    }
+}
+
+impl From<JsonTimings> for BenchStats {
+    fn from(timings: JsonTimings) -> Self {
+        BenchStats {
+            mean: Duration::from_nanos(timings.nanos),
+            median: Duration::from_nanos(timings.median_nanos),
+            p95: Duration::from_nanos(timings.p95_nanos),
+            samples: timings.samples.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Phase {
+    Parse,
+}
+
+/// A line printed with `--format json`, read back by `cargo all` and `cargo stats`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Record {
+    Part {
+        part: u8,
+        /// The submitted string, e.g. the letters of ASCII art.
+        answer: Option<String>,
+        #[serde(flatten)]
+        timings: JsonTimings,
+        #[serde(default)]
+        memo_hits: u64,
+        #[serde(default)]
+        memo_misses: u64,
+    },
+    Parse {
+        phase: Phase,
+        #[serde(flatten)]
+        timings: JsonTimings,
+    },
+    Memory {
+        peak_memory_bytes: u64,
+    },
+}
+
+impl Record {
+    /// Parses a line of output, `None` for lines printed by the solution itself.
+    #[must_use]
+    pub fn parse(line: &str) -> Option<Self> {
+        if !line.starts_with('{') {
+            return None;
+        }
+        serde_json::from_str(line).ok()
+    }
+
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("records serialize to JSON")
+    }
+}
+
+pub(crate) fn format_json<T: Answer>(
+    part: u8,
+    result: Option<&T>,
+    stats: &BenchStats,
+    memo_stats: &MemoStats,
+) -> String {
+    Record::Part {
+        part,
+        answer: result.map(|result| result.submission().unwrap_or_else(|| result.to_string())),
+        timings: stats.into(),
+        memo_hits: memo_stats.hits,
+        memo_misses: memo_stats.misses,
+    }
+    .to_json()
+}
+
+/// JSON line of the parse phase, e.g. `{"phase":"parse","nanos":1500,...}`.
+pub(crate) fn format_parse_json(stats: &BenchStats) -> String {
+    Record::Parse {
+        phase: Phase::Parse,
+        timings: stats.into(),
+    }
+    .to_json()
+}
+
+pub(crate) fn format_memory_json(bytes: u64) -> String {
+    Record::Memory {
+        peak_memory_bytes: bytes,
+    }
+    .to_json()
+}
+
+fn print_viz_summary(recorder: &Recorder) {
+    let frames = recorder.frames();
+
+    match recorder.output() {
+        None => eprintln!("{ANSI_ITALIC}viz: {frames} frames{ANSI_RESET}"),
+        Some(path) => eprintln!(
+            "{ANSI_ITALIC}viz: {frames} frames written to \"{}\"{ANSI_RESET}",
+            path.display()
+        ),
+    }
+}
+
+/// Spread of benchmarked runs, only printed with `--time`.
+pub(crate) fn print_bench_stats(stats: &BenchStats) {
+    if stats.samples == 1 {
+        return;
+    }
+
+    println!(
+        "  {ANSI_ITALIC}bench: median {:.1?}, p95 {:.1?}{ANSI_RESET}",
+        stats.median, stats.p95
+    );
+}
+
+/// Prints the peak memory use of the process with `--time`, in kB. Only available on Linux.
+pub fn print_peak_memory(options: &RunOptions) {
+    if !options.time {
+        return;
+    }
+
+    let Some(bytes) = peak_memory() else {
+        return;
+    };
+
+    match options.format() {
+        OutputFormat::Text => println!("Peak memory: {}", format_bytes(bytes)),
+        OutputFormat::Json => println!("{}", format_memory_json(bytes)),
+    }
+}
+
+/// Peak resident memory of the process in bytes, read from `/proc`.
+fn peak_memory() -> Option<u64> {
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+    let kilobytes = status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmHWM:"))?
+        .trim()
+        .strip_suffix("kB")?
+        .trim()
+        .parse::<u64>()
+        .ok()?;
+
+    Some(kilobytes * 1024)
+}
+
+/// Bytes in binary units, e.g. `4.1 MiB`.
+#[must_use]
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+    #[allow(clippy::cast_precision_loss)]
+    let mut value = bytes as f64;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+fn print_memo_stats(stats: &MemoStats) {
+    if stats.lookups() == 0 {
+        return;
+    }
+
+    println!(
+        "  {ANSI_ITALIC}memo: {} hits, {} misses ({:.1}% hit rate){ANSI_RESET}",
+        stats.hits,
+        stats.misses,
+        stats.hit_rate() * 100.0
+    );
+}
+
+pub(crate) fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
@@ -127,33 +674,29 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
+/// Try to submit one part of the solution if `--submit` names this part.
+/// With the `manual` backend of `aoc.toml`, the answer is printed with the puzzle URL instead.
+fn submit_result<T: Answer>(
     result: T,
     day: Day,
     part: u8,
+    options: &RunOptions,
 ) -> Option<Result<Output, aoc_cli::AocCommandError>> {
-    let args: Vec<String> = env::args().collect();
-
-    if !args.contains(&"--submit".into()) {
+    if options.submit != Some(part) {
         return None;
     }
 
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
-    }
-
-    let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
-
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
+    let Some(submission) = result.submission() else {
+        eprintln!("Could not read the answer of part {part}, it was not submitted.");
         process::exit(1);
     };
 
-    if part_submit != part {
+    if config::get().submit.backend == SubmitBackend::Manual {
+        let year = aoc_cli::get_year().map_or("<year>".into(), |year| year.to_string());
+        println!(
+            "Submit {ANSI_BOLD}{submission}{ANSI_RESET} at https://adventofcode.com/{year}/day/{}",
+            day.into_inner()
+        );
         return None;
     }
 
@@ -163,5 +706,187 @@ fn submit_result<T: Display>(
     }
 
     println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    Some(aoc_cli::submit(day, part, &submission))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        bench_iterations, format_json, run_timed, BenchStats, InputSource, OutputFormat, RunOptions,
+    };
+    use crate::template::answer::LetterArt;
+    use crate::template::memo::MemoStats;
+    use crate::template::viz::VizMode;
+    use clap::Parser;
+    use std::cell::Cell;
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    #[test]
+    fn parses_defaults() {
+        let options = RunOptions::try_parse_from(["01"]).unwrap();
+        assert_eq!(options, RunOptions::default());
+        assert!(options.to_args().is_empty());
+        assert_eq!(options.input, InputSource::Inputs);
+        assert_eq!(options.bench_budget, None);
+    }
+
+    #[test]
+    fn parses_flags() {
+        let options = RunOptions::try_parse_from([
+            "01",
+            "--time",
+            "--submit",
+            "2",
+            "--bench-budget",
+            "250",
+            "--input",
+            "examples",
+            "--format",
+            "json",
+            "--log",
+            "debug",
+            "--viz",
+            "ppm",
+            "--viz-max-frames",
+            "3",
+            "--param",
+            "expansion=100",
+            "--param",
+            "label=a=b",
+        ])
+        .unwrap();
+
+        assert!(options.time);
+        assert_eq!(options.submit, Some(2));
+        assert_eq!(options.bench_budget(), Duration::from_millis(250));
+        assert_eq!(options.input, InputSource::Examples);
+        assert_eq!(options.format(), OutputFormat::Json);
+        assert_eq!(options.log.as_deref(), Some("debug"));
+
+        let viz = options.viz_options().unwrap();
+        assert_eq!(viz.mode, VizMode::Ppm);
+        assert_eq!(viz.max_frames, 3);
+        assert_eq!(viz.fps, 10);
+        assert_eq!(
+            options.params,
+            [
+                ("expansion".to_string(), "100".to_string()),
+                ("label".to_string(), "a=b".to_string())
+            ]
+        );
+
+        let forwarded =
+            RunOptions::try_parse_from(std::iter::once("01".to_string()).chain(options.to_args()))
+                .unwrap();
+        assert_eq!(forwarded, options);
+    }
+
+    #[test]
+    fn parses_input_paths() {
+        let options = RunOptions::try_parse_from(["01", "--input", "/tmp/input.txt"]).unwrap();
+        assert_eq!(
+            options.input,
+            InputSource::File(PathBuf::from("/tmp/input.txt"))
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_flags() {
+        assert!(RunOptions::try_parse_from(["01", "--submit", "3"]).is_err());
+        assert!(RunOptions::try_parse_from(["01", "--format", "xml"]).is_err());
+        assert!(RunOptions::try_parse_from(["01", "--unknown"]).is_err());
+        assert!(RunOptions::try_parse_from(["01", "--viz-fps", "30"]).is_err());
+        assert!(RunOptions::try_parse_from(["01", "--param", "expansion"]).is_err());
+        assert!(RunOptions::try_parse_from(["01", "--param", "=100"]).is_err());
+    }
+
+    #[test]
+    fn runs_once_without_time() {
+        let calls = Cell::new(0);
+        let options = RunOptions::default();
+
+        let (result, stats, _) = run_timed(
+            |x: u32| {
+                calls.set(calls.get() + 1);
+                x * 2
+            },
+            21,
+            &options,
+            |_| {},
+        );
+
+        assert_eq!(result, 42);
+        assert_eq!(stats.samples, 1);
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[test]
+    fn benches_with_time() {
+        let calls = Cell::new(0);
+        let options = RunOptions {
+            time: true,
+            bench_budget: Some(1),
+            format: Some(OutputFormat::Json),
+            ..RunOptions::default()
+        };
+
+        let (_, stats, _) = run_timed(|()| calls.set(calls.get() + 1), (), &options, |_| {});
+
+        assert!(stats.samples >= 10);
+        assert_eq!(calls.get(), stats.samples + 1);
+    }
+
+    #[test]
+    fn scales_iterations_to_budget() {
+        let budget = Duration::from_secs(1);
+
+        assert_eq!(bench_iterations(&Duration::from_millis(1), &budget), 1000);
+        assert_eq!(bench_iterations(&Duration::from_secs(2), &budget), 10);
+        assert_eq!(bench_iterations(&Duration::ZERO, &budget), 10000);
+    }
+
+    #[test]
+    fn computes_bench_stats() {
+        let stats = BenchStats::from_samples((1..=20).rev().map(Duration::from_millis).collect());
+
+        assert_eq!(stats.mean, Duration::from_micros(10500));
+        assert_eq!(stats.median, Duration::from_millis(10));
+        assert_eq!(stats.p95, Duration::from_millis(19));
+        assert_eq!(stats.samples, 20);
+
+        let single = BenchStats::from_samples(vec![Duration::from_nanos(1500)]);
+        assert_eq!(single.median, single.mean);
+        assert_eq!(single.p95, single.mean);
+    }
+
+    #[test]
+    fn formats_json() {
+        let stats = MemoStats { hits: 3, misses: 1 };
+        let bench = BenchStats {
+            mean: Duration::from_nanos(1500),
+            median: Duration::from_nanos(1400),
+            p95: Duration::from_nanos(2000),
+            samples: 10,
+        };
+        let once = BenchStats::from_samples(vec![Duration::ZERO]);
+
+        assert_eq!(
+            format_json(1, Some(&"a\"b\nc"), &bench, &stats),
+            r#"{"part":1,"answer":"a\"b\nc","nanos":1500,"samples":10,"median_nanos":1400,"p95_nanos":2000,"memo_hits":3,"memo_misses":1}"#
+        );
+        assert_eq!(
+            format_json(
+                1,
+                Some(&LetterArt("###\n.#.\n.#.\n.#.\n.#.\n###".into())),
+                &once,
+                &MemoStats::default()
+            ),
+            r#"{"part":1,"answer":"I","nanos":0,"samples":1,"median_nanos":0,"p95_nanos":0,"memo_hits":0,"memo_misses":0}"#
+        );
+        assert_eq!(
+            format_json::<u32>(2, None, &once, &MemoStats::default()),
+            r#"{"part":2,"answer":null,"nanos":0,"samples":1,"median_nanos":0,"p95_nanos":0,"memo_hits":0,"memo_misses":0}"#
+        );
+    }
 }
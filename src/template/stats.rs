@@ -0,0 +1,415 @@
+/// Module that summarises all solutions for the `stats` command.
+/// Reports are rendered as a coloured terminal table or as markdown, e.g. to paste into the readme.
+use std::{fmt::Write as _, time::Duration};
+
+use crate::template::{
+    readme_benchmarks::{self, format_time, TimeUnit, Timings},
+    ANSI_BOLD, ANSI_RESET,
+};
+use crate::Day;
+
+const ANSI_RED: &str = "\x1b[31m";
+const ANSI_GREEN: &str = "\x1b[32m";
+
+/// Crates whose use is reported per solution.
+pub const TRACKED_CRATES: [&str; 3] = ["rayon", "indicatif", "regex"];
+
+/// Number of parts listed as the slowest.
+const SLOWEST_PARTS: usize = 5;
+
+/// Everything known about the solution of a day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayStats {
+    pub timings: Timings,
+    /// Answers of both parts, `None` for unsolved parts.
+    pub answers: [Option<String>; 2],
+    /// Tracked crates used by the solution.
+    pub crates: Vec<&'static str>,
+}
+
+impl DayStats {
+    #[must_use]
+    pub fn day(&self) -> Day {
+        self.timings.day
+    }
+
+    #[must_use]
+    pub fn solved_parts(&self) -> usize {
+        self.answers.iter().flatten().count()
+    }
+}
+
+/// Tracked crates referenced by the code of a solution, tests excluded.
+#[must_use]
+pub fn find_crates(source: &str) -> Vec<&'static str> {
+    let code: Vec<&str> = readme_benchmarks::code_lines(source).collect();
+
+    TRACKED_CRATES
+        .into_iter()
+        .filter(|krate| {
+            let path = format!("{krate}::");
+            code.iter().any(|line| line.contains(&path))
+        })
+        .collect()
+}
+
+/// The summary of all solutions, rendered by [`Report::to_terminal`] and [`Report::to_markdown`].
+pub struct Report {
+    days: Vec<DayStats>,
+    budget: Duration,
+}
+
+/// Cells of the per-day table, with whether the day is over budget.
+struct Row {
+    cells: Vec<String>,
+    solved: [bool; 2],
+    over_budget: bool,
+}
+
+impl Report {
+    #[must_use]
+    pub fn new(days: Vec<DayStats>, budget: Duration) -> Self {
+        Report { days, budget }
+    }
+
+    #[must_use]
+    pub fn total(&self) -> Duration {
+        self.days.iter().map(|day| day.timings.total()).sum()
+    }
+
+    fn share(&self, time: Duration) -> f64 {
+        let total = self.total();
+        if total.is_zero() {
+            0.0
+        } else {
+            time.as_secs_f64() / total.as_secs_f64() * 100.0
+        }
+    }
+
+    fn headers() -> Vec<&'static str> {
+        vec![
+            "Day", "Part 1", "Part 2", "Time", "Share", "Lines", "Crates",
+        ]
+    }
+
+    fn rows(&self) -> Vec<Row> {
+        self.days
+            .iter()
+            .map(|day| {
+                let answer = |part: usize| {
+                    day.answers[part]
+                        .as_deref()
+                        .map_or_else(|| "✖".into(), format_answer)
+                };
+                let total = day.timings.total();
+
+                Row {
+                    cells: vec![
+                        day.day().to_string(),
+                        answer(0),
+                        answer(1),
+                        format_time(total, TimeUnit::Auto, 1),
+                        format!("{:.1}%", self.share(total)),
+                        day.timings
+                            .lines_of_code
+                            .map_or_else(|| "-".into(), |lines| lines.to_string()),
+                        if day.crates.is_empty() {
+                            "-".into()
+                        } else {
+                            day.crates.join(", ")
+                        },
+                    ],
+                    solved: [day.answers[0].is_some(), day.answers[1].is_some()],
+                    over_budget: total > self.budget,
+                }
+            })
+            .collect()
+    }
+
+    /// The slowest parts of all days, slowest first.
+    fn slowest_parts(&self) -> Vec<(Day, u8, Duration)> {
+        let mut parts: Vec<(Day, u8, Duration)> = self
+            .days
+            .iter()
+            .flat_map(|day| {
+                (1..=2).filter_map(|part| {
+                    day.timings
+                        .part(part)
+                        .map(|stats| (day.day(), part, stats.mean))
+                })
+            })
+            .collect();
+
+        parts.sort_by_key(|&(_, _, time)| std::cmp::Reverse(time));
+        parts.truncate(SLOWEST_PARTS);
+        parts
+    }
+
+    /// Days that take longer than the budget, slowest first.
+    fn hall_of_shame(&self) -> Vec<(Day, Duration)> {
+        let mut days: Vec<(Day, Duration)> = self
+            .days
+            .iter()
+            .map(|day| (day.day(), day.timings.total()))
+            .filter(|&(_, time)| time > self.budget)
+            .collect();
+
+        days.sort_by_key(|&(_, time)| std::cmp::Reverse(time));
+        days
+    }
+
+    fn summary(&self) -> String {
+        let stars: usize = self.days.iter().map(DayStats::solved_parts).sum();
+        format!(
+            "{stars} of {} parts solved in {}",
+            self.days.len() * 2,
+            format_time(self.total(), TimeUnit::Auto, 2)
+        )
+    }
+
+    fn format_slowest(&self, (day, part, time): (Day, u8, Duration)) -> String {
+        format!(
+            "Day {day} part {part}: {} ({:.1}%)",
+            format_time(time, TimeUnit::Auto, 1),
+            self.share(time)
+        )
+    }
+
+    fn format_shame(&self, (day, time): (Day, Duration)) -> String {
+        format!(
+            "Day {day}: {} ({:.1}× the budget)",
+            format_time(time, TimeUnit::Auto, 1),
+            time.as_secs_f64() / self.budget.as_secs_f64()
+        )
+    }
+
+    fn budget_str(&self) -> String {
+        format_time(self.budget, TimeUnit::Auto, 0)
+    }
+
+    /// A table with solved parts in green, unsolved parts and days over budget in red.
+    #[must_use]
+    pub fn to_terminal(&self) -> String {
+        let headers = Report::headers();
+        let rows = self.rows();
+
+        let widths: Vec<usize> = (0..headers.len())
+            .map(|i| {
+                rows.iter()
+                    .map(|row| row.cells[i].chars().count())
+                    .chain([headers[i].len()])
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        let pad = |s: &str, width: usize| format!("{s}{}", " ".repeat(width - s.chars().count()));
+
+        let mut s = String::new();
+
+        let header: Vec<String> = headers
+            .iter()
+            .zip(&widths)
+            .map(|(header, &width)| pad(header, width))
+            .collect();
+        let _ = writeln!(s, "{ANSI_BOLD}{}{ANSI_RESET}", header.join("  ").trim_end());
+
+        for row in &rows {
+            let cells: Vec<String> = row
+                .cells
+                .iter()
+                .zip(&widths)
+                .enumerate()
+                .map(|(i, (cell, &width))| {
+                    let cell = pad(cell, width);
+                    let color = match i {
+                        1 | 2 if row.solved[i - 1] => Some(ANSI_GREEN),
+                        1 | 2 => Some(ANSI_RED),
+                        3 if row.over_budget => Some(ANSI_RED),
+                        _ => None,
+                    };
+                    color.map_or(cell.clone(), |color| format!("{color}{cell}{ANSI_RESET}"))
+                })
+                .collect();
+            let _ = writeln!(s, "{}", cells.join("  ").trim_end());
+        }
+
+        let _ = writeln!(s, "\n{}", self.summary());
+
+        let _ = writeln!(s, "\n{ANSI_BOLD}Slowest parts{ANSI_RESET}");
+        for part in self.slowest_parts() {
+            let _ = writeln!(s, "  {}", self.format_slowest(part));
+        }
+
+        let _ = writeln!(
+            s,
+            "\n{ANSI_BOLD}Hall of shame{ANSI_RESET} (over {})",
+            self.budget_str()
+        );
+        let shame = self.hall_of_shame();
+        if shame.is_empty() {
+            let _ = writeln!(s, "  {ANSI_GREEN}none{ANSI_RESET}");
+        }
+        for day in shame {
+            let _ = writeln!(s, "  {ANSI_RED}{}{ANSI_RESET}", self.format_shame(day));
+        }
+
+        s
+    }
+
+    #[must_use]
+    pub fn to_markdown(&self) -> String {
+        let headers = Report::headers();
+
+        let mut lines = vec![
+            format!("| {} |", headers.join(" | ")),
+            format!("|{}", " :---: |".repeat(headers.len())),
+        ];
+
+        for row in self.rows() {
+            let cells: Vec<String> = row
+                .cells
+                .iter()
+                .enumerate()
+                .map(|(i, cell)| match i {
+                    1 | 2 if row.solved[i - 1] => format!("`{}`", cell.replace('|', "\\|")),
+                    3 if row.over_budget => format!("**{cell}**"),
+                    _ => cell.clone(),
+                })
+                .collect();
+            lines.push(format!("| {} |", cells.join(" | ")));
+        }
+
+        lines.push(String::new());
+        lines.push(format!("{}.", self.summary()));
+
+        lines.push(String::new());
+        lines.push("**Slowest parts**".into());
+        lines.push(String::new());
+        for part in self.slowest_parts() {
+            lines.push(format!("- {}", self.format_slowest(part)));
+        }
+
+        lines.push(String::new());
+        lines.push(format!("**Hall of shame** (over {})", self.budget_str()));
+        lines.push(String::new());
+        let shame = self.hall_of_shame();
+        if shame.is_empty() {
+            lines.push("- none".into());
+        }
+        for day in shame {
+            lines.push(format!("- {}", self.format_shame(day)));
+        }
+
+        lines.push(String::new());
+        lines.join("\n")
+    }
+}
+
+/// First line of an answer, shortened to fit a table cell.
+fn format_answer(answer: &str) -> String {
+    const MAX_CHARS: usize = 20;
+
+    let mut lines = answer.lines();
+    let first = lines.next().unwrap_or_default();
+    let is_cut = lines.next().is_some() || first.chars().count() > MAX_CHARS;
+
+    if is_cut {
+        format!("{}…", first.chars().take(MAX_CHARS - 1).collect::<String>())
+    } else {
+        first.into()
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{find_crates, DayStats, Report};
+    use crate::day;
+    use crate::template::readme_benchmarks::Timings;
+    use crate::template::runner::BenchStats;
+    use std::time::Duration;
+
+    fn ms(millis: u64) -> Option<BenchStats> {
+        Some(BenchStats::from_samples(vec![Duration::from_millis(
+            millis,
+        )]))
+    }
+
+    fn get_mock_report() -> Report {
+        Report::new(
+            vec![
+                DayStats {
+                    timings: Timings {
+                        part_1: ms(100),
+                        part_2: ms(1400),
+                        lines_of_code: Some(80),
+                        ..Timings::new(day!(1))
+                    },
+                    answers: [Some("42".into()), Some("a|b".into())],
+                    crates: vec!["rayon", "regex"],
+                },
+                DayStats {
+                    timings: Timings {
+                        part_1: ms(500),
+                        ..Timings::new(day!(2))
+                    },
+                    answers: [Some("a very long answer that does not fit".into()), None],
+                    crates: vec![],
+                },
+            ],
+            Duration::from_secs(1),
+        )
+    }
+
+    #[test]
+    fn renders_markdown() {
+        let expected = [
+            "| Day | Part 1 | Part 2 | Time | Share | Lines | Crates |",
+            "| :---: | :---: | :---: | :---: | :---: | :---: | :---: |",
+            "| 01 | `42` | `a\\|b` | **1.5s** | 75.0% | 80 | rayon, regex |",
+            "| 02 | `a very long answer …` | ✖ | 500.0ms | 25.0% | - | - |",
+            "",
+            "3 of 4 parts solved in 2.00s.",
+            "",
+            "**Slowest parts**",
+            "",
+            "- Day 01 part 2: 1.4s (70.0%)",
+            "- Day 02 part 1: 500.0ms (25.0%)",
+            "- Day 01 part 1: 100.0ms (5.0%)",
+            "",
+            "**Hall of shame** (over 1s)",
+            "",
+            "- Day 01: 1.5s (1.5× the budget)",
+            "",
+        ]
+        .join("\n");
+
+        assert_eq!(get_mock_report().to_markdown(), expected);
+    }
+
+    #[test]
+    fn renders_terminal() {
+        let terminal = get_mock_report().to_terminal();
+
+        // columns are aligned on the visible width of cells.
+        assert!(terminal.contains(&format!("\x1b[32m42{}\x1b[0m", " ".repeat(18))));
+        assert!(terminal.contains("\x1b[31m✖     \x1b[0m"));
+        assert!(terminal.contains("\x1b[31m1.5s   \x1b[0m"));
+        assert!(terminal.contains("3 of 4 parts solved in 2.00s"));
+        assert!(terminal.contains("\x1b[31mDay 01: 1.5s (1.5× the budget)\x1b[0m"));
+    }
+
+    #[test]
+    fn finds_crates() {
+        let source = [
+            "use rayon::prelude::*;",
+            "// regex::Regex is not needed",
+            "fn main() { let _ = indicatif::ProgressBar::hidden(); }",
+            "#[cfg(test)]",
+            "mod tests { use regex::Regex; }",
+        ]
+        .join("\n");
+
+        assert_eq!(find_crates(&source), ["rayon", "indicatif"]);
+    }
+}
@@ -0,0 +1,620 @@
+/// Module that lets solutions emit frames of a grid to debug them visually.
+/// Frames are only built and written when the binary runs with `--viz`, otherwise `emit` returns immediately.
+use std::{
+    fmt::Write as _,
+    fs::{self, File},
+    io::{self, stderr, BufWriter, Write},
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use clap::ValueEnum;
+
+use crate::template::{ANSI_BOLD, ANSI_RESET};
+use crate::Day;
+
+const ANSI_CLEAR: &str = "\x1b[2J\x1b[H";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum VizMode {
+    /// Redraw frames in the terminal.
+    Terminal,
+    /// Write one PPM image per frame to `target/viz/`.
+    Ppm,
+    /// Write one PNG image per frame to `target/viz/`.
+    Png,
+    /// Write the frames of each part as one animated GIF to `target/viz/`, played at `--viz-fps`.
+    Gif,
+}
+
+impl std::fmt::Display for VizMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VizMode::Terminal => write!(f, "terminal"),
+            VizMode::Ppm => write!(f, "ppm"),
+            VizMode::Png => write!(f, "png"),
+            VizMode::Gif => write!(f, "gif"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+}
+
+impl Color {
+    fn ansi(self) -> &'static str {
+        match self {
+            Color::Red => "\x1b[31m",
+            Color::Green => "\x1b[32m",
+            Color::Yellow => "\x1b[33m",
+            Color::Blue => "\x1b[34m",
+            Color::Magenta => "\x1b[35m",
+            Color::Cyan => "\x1b[36m",
+        }
+    }
+
+    /// Index of the colour in [`PALETTE`].
+    fn index(self) -> u8 {
+        match self {
+            Color::Red => 2,
+            Color::Green => 3,
+            Color::Yellow => 4,
+            Color::Blue => 5,
+            Color::Magenta => 6,
+            Color::Cyan => 7,
+        }
+    }
+}
+
+/// Colours of images: empty cells (`.` and spaces) are dark, other cells light, highlighted cells take their colour.
+const PALETTE: [[u8; 3]; 8] = [
+    [30, 30, 30],
+    [200, 200, 200],
+    [220, 50, 47],
+    [133, 153, 0],
+    [181, 137, 0],
+    [38, 139, 210],
+    [211, 54, 130],
+    [42, 161, 152],
+];
+
+/// A grid of characters, some of them highlighted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    width: usize,
+    height: usize,
+    cells: Vec<char>,
+    colors: Vec<Option<Color>>,
+}
+
+impl Frame {
+    /// Builds a frame from rows of cells. Shorter rows are padded with spaces.
+    pub fn from_grid<T>(grid: &[Vec<T>], to_char: impl Fn(&T) -> char) -> Self {
+        let width = grid.iter().map(Vec::len).max().unwrap_or(0);
+        let height = grid.len();
+
+        let cells = grid
+            .iter()
+            .flat_map(|row| {
+                row.iter()
+                    .map(&to_char)
+                    .chain(std::iter::repeat(' '))
+                    .take(width)
+            })
+            .collect();
+
+        Frame {
+            width,
+            height,
+            cells,
+            colors: vec![None; width * height],
+        }
+    }
+
+    /// Colours the cell at `(x, y)`. Positions outside of the frame are ignored.
+    #[must_use]
+    pub fn highlight(mut self, (x, y): (usize, usize), color: Color) -> Self {
+        if x < self.width && y < self.height {
+            self.colors[y * self.width + x] = Some(color);
+        }
+        self
+    }
+
+    #[must_use]
+    pub fn highlight_all(
+        self,
+        positions: impl IntoIterator<Item = (usize, usize)>,
+        color: Color,
+    ) -> Self {
+        positions
+            .into_iter()
+            .fold(self, |frame, position| frame.highlight(position, color))
+    }
+
+    fn rows(&self) -> impl Iterator<Item = (&[char], &[Option<Color>])> {
+        self.cells
+            .chunks(self.width.max(1))
+            .zip(self.colors.chunks(self.width.max(1)))
+    }
+
+    /// The frame as text, highlighted cells wrapped in ANSI colours.
+    #[must_use]
+    pub fn to_ansi(&self) -> String {
+        let mut s = String::new();
+
+        for (cells, colors) in self.rows() {
+            for (c, color) in cells.iter().zip(colors) {
+                match color {
+                    Some(color) => {
+                        let _ = write!(s, "{ANSI_BOLD}{}{c}{ANSI_RESET}", color.ansi());
+                    }
+                    None => s.push(*c),
+                }
+            }
+            s.push('\n');
+        }
+
+        s
+    }
+
+    /// Size of the images of the frame with `scale` × `scale` pixels per cell.
+    #[must_use]
+    pub fn image_size(&self, scale: usize) -> (usize, usize) {
+        (self.width * scale, self.height * scale)
+    }
+
+    /// The pixels of the frame as indices of [`PALETTE`], row by row.
+    fn to_indexed(&self, scale: usize) -> Vec<u8> {
+        let mut pixels = Vec::with_capacity(self.width * self.height * scale * scale);
+
+        for (cells, colors) in self.rows() {
+            let row: Vec<u8> = cells
+                .iter()
+                .zip(colors)
+                .flat_map(|(c, color)| {
+                    let index = match (color, c) {
+                        (Some(color), _) => color.index(),
+                        (None, '.' | ' ') => 0,
+                        (None, _) => 1,
+                    };
+                    [index].repeat(scale)
+                })
+                .collect();
+
+            for _ in 0..scale {
+                pixels.extend_from_slice(&row);
+            }
+        }
+
+        pixels
+    }
+
+    fn to_rgb(&self, scale: usize) -> Vec<u8> {
+        self.to_indexed(scale)
+            .into_iter()
+            .flat_map(|index| PALETTE[usize::from(index)])
+            .collect()
+    }
+
+    /// The frame as a binary PPM image with `scale` × `scale` pixels per cell.
+    #[must_use]
+    pub fn to_ppm(&self, scale: usize) -> Vec<u8> {
+        let (width, height) = self.image_size(scale);
+        let mut image = format!("P6\n{width} {height}\n255\n").into_bytes();
+        image.extend(self.to_rgb(scale));
+        image
+    }
+
+    /// The frame as a PNG image with `scale` × `scale` pixels per cell.
+    pub fn to_png(&self, scale: usize) -> io::Result<Vec<u8>> {
+        let (width, height) = self.image_size(scale);
+        let mut image = vec![];
+
+        let mut encoder = png::Encoder::new(&mut image, dimension(width)?, dimension(height)?);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut writer = encoder.write_header().map_err(io::Error::other)?;
+        writer
+            .write_image_data(&self.to_rgb(scale))
+            .map_err(io::Error::other)?;
+        writer.finish().map_err(io::Error::other)?;
+
+        Ok(image)
+    }
+
+    /// The pixels of the frame cropped or padded with empty cells to `width` × `height`, for frames of a GIF.
+    fn to_indexed_sized(&self, scale: usize, (width, height): (usize, usize)) -> Vec<u8> {
+        let pixels = self.to_indexed(scale);
+        let mut sized = vec![0; width * height];
+
+        for (y, row) in pixels.chunks(self.width * scale).take(height).enumerate() {
+            let len = row.len().min(width);
+            sized[y * width..y * width + len].copy_from_slice(&row[..len]);
+        }
+
+        sized
+    }
+}
+
+/// Converts the size of an image for an encoder, failing for images too large for its format.
+fn dimension<T: TryFrom<usize>>(n: usize) -> io::Result<T> {
+    T::try_from(n).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("image too large: {n} pixels wide or high"),
+        )
+    })
+}
+
+/// Settings of `--viz`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VizOptions {
+    pub mode: VizMode,
+    pub fps: u32,
+    pub max_frames: usize,
+    pub dir: PathBuf,
+    pub scale: usize,
+}
+
+impl Default for VizOptions {
+    fn default() -> Self {
+        VizOptions {
+            mode: VizMode::Terminal,
+            fps: 10,
+            max_frames: 500,
+            dir: PathBuf::from("target/viz"),
+            scale: 4,
+        }
+    }
+}
+
+/// An animated GIF being written. Its frames take the size of the first one.
+struct Gif {
+    encoder: gif::Encoder<BufWriter<File>>,
+    size: (usize, usize),
+}
+
+/// Writes the frames of one part.
+pub struct Recorder {
+    options: VizOptions,
+    prefix: String,
+    frames: usize,
+    last_frame: Option<Instant>,
+    gif: Option<Gif>,
+}
+
+impl Recorder {
+    #[must_use]
+    pub fn new(options: VizOptions, prefix: &str) -> Self {
+        Recorder {
+            options,
+            prefix: prefix.into(),
+            frames: 0,
+            last_frame: None,
+            gif: None,
+        }
+    }
+
+    /// Number of frames written so far.
+    #[must_use]
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    fn is_full(&self) -> bool {
+        self.frames >= self.options.max_frames
+    }
+
+    /// Path of the n-th frame in PPM and PNG mode.
+    #[must_use]
+    pub fn frame_path(&self, index: usize) -> PathBuf {
+        self.options
+            .dir
+            .join(format!("{}-{index:05}.{}", self.prefix, self.options.mode))
+    }
+
+    /// Path of the animation in GIF mode.
+    #[must_use]
+    pub fn gif_path(&self) -> PathBuf {
+        self.options.dir.join(format!("{}.gif", self.prefix))
+    }
+
+    /// Where the frames were written, `None` in the terminal.
+    #[must_use]
+    pub fn output(&self) -> Option<PathBuf> {
+        match self.options.mode {
+            VizMode::Terminal => None,
+            VizMode::Ppm | VizMode::Png => Some(self.options.dir.clone()),
+            VizMode::Gif => Some(self.gif_path()),
+        }
+    }
+
+    /// Writes a frame, unless the frame cap is reached. Returns whether the frame was written.
+    pub fn record(&mut self, frame: &Frame) -> io::Result<bool> {
+        if self.is_full() {
+            return Ok(false);
+        }
+
+        match self.options.mode {
+            VizMode::Terminal => {
+                if let (Some(last), Some(interval)) = (self.last_frame, self.interval()) {
+                    thread::sleep(interval.saturating_sub(last.elapsed()));
+                }
+
+                // frames go to stderr, like progress bars, so that results on stdout stay parsable.
+                let mut stderr = stderr().lock();
+                write!(stderr, "{ANSI_CLEAR}{}", frame.to_ansi())?;
+                writeln!(stderr, "frame {}", self.frames + 1)?;
+                stderr.flush()?;
+            }
+            VizMode::Ppm => {
+                fs::create_dir_all(&self.options.dir)?;
+                fs::write(
+                    self.frame_path(self.frames),
+                    frame.to_ppm(self.options.scale),
+                )?;
+            }
+            VizMode::Png => {
+                fs::create_dir_all(&self.options.dir)?;
+                fs::write(
+                    self.frame_path(self.frames),
+                    frame.to_png(self.options.scale)?,
+                )?;
+            }
+            VizMode::Gif => self.record_gif(frame)?,
+        }
+
+        self.last_frame = Some(Instant::now());
+        self.frames += 1;
+
+        Ok(true)
+    }
+
+    fn interval(&self) -> Option<Duration> {
+        (self.options.fps > 0).then(|| Duration::from_secs(1) / self.options.fps)
+    }
+
+    fn record_gif(&mut self, frame: &Frame) -> io::Result<()> {
+        let scale = self.options.scale;
+
+        if self.gif.is_none() {
+            let size = frame.image_size(scale);
+            fs::create_dir_all(&self.options.dir)?;
+            let file = BufWriter::new(File::create(self.gif_path())?);
+
+            let palette: Vec<u8> = PALETTE.concat();
+            let mut encoder =
+                gif::Encoder::new(file, dimension(size.0)?, dimension(size.1)?, &palette)
+                    .map_err(io::Error::other)?;
+            encoder
+                .set_repeat(gif::Repeat::Infinite)
+                .map_err(io::Error::other)?;
+
+            self.gif = Some(Gif { encoder, size });
+        }
+
+        // delays are in hundredths of a second.
+        let delay = self.interval().map_or(0, |interval| {
+            u16::try_from(interval.as_millis() / 10).unwrap_or(u16::MAX)
+        });
+
+        let gif = self.gif.as_mut().expect("the GIF was created above");
+        let mut image = gif::Frame::from_indexed_pixels(
+            dimension(gif.size.0)?,
+            dimension(gif.size.1)?,
+            frame.to_indexed_sized(scale, gif.size),
+            None,
+        );
+        image.delay = delay;
+
+        gif.encoder.write_frame(&image).map_err(io::Error::other)
+    }
+
+    /// Completes the files of the part, i.e. the GIF.
+    pub fn finish(&mut self) -> io::Result<()> {
+        match self.gif.take() {
+            Some(gif) => gif.encoder.into_inner()?.flush(),
+            None => Ok(()),
+        }
+    }
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static RECORDER: Mutex<Option<Recorder>> = Mutex::new(None);
+
+/// Whether frames are currently recorded. Use it to skip work that only feeds `emit`.
+#[must_use]
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Records the frame built by `frame`. The closure is not called when `--viz` is not set.
+pub fn emit(frame: impl FnOnce() -> Frame) {
+    if !enabled() {
+        return;
+    }
+
+    let mut recorder = RECORDER.lock().unwrap();
+    let Some(recorder) = recorder.as_mut().filter(|recorder| !recorder.is_full()) else {
+        return;
+    };
+
+    if let Err(e) = recorder.record(&frame()) {
+        eprintln!("Failed to write visualisation frame: {e}");
+        ENABLED.store(false, Ordering::Relaxed);
+    }
+}
+
+/// Records a process of `steps` steps, e.g. a path growing one tile per step, in as many frames as the cap has room for.
+/// `frame` builds the frame after a number of steps, evenly spread from the first steps to all of them, so the end of
+/// long processes is recorded too. Emit the steps once they are known rather than calling `emit` at every step.
+pub fn emit_steps(steps: usize, frame: impl Fn(usize) -> Frame) {
+    if !enabled() {
+        return;
+    }
+
+    let room = match RECORDER.lock().unwrap().as_ref() {
+        Some(recorder) => recorder.options.max_frames.saturating_sub(recorder.frames),
+        None => return,
+    };
+
+    for step in sample_steps(steps, room) {
+        emit(|| frame(step));
+    }
+}
+
+/// Up to `frames` numbers of steps in `1..=steps`, evenly spread and ending with `steps`.
+fn sample_steps(steps: usize, frames: usize) -> impl Iterator<Item = usize> {
+    let frames = frames.min(steps);
+    (1..=frames).map(move |frame| frame * steps / frames)
+}
+
+/// Starts recording the frames of a part. Called by the runner.
+pub fn begin(day: Day, part: u8, options: VizOptions) {
+    *RECORDER.lock().unwrap() = Some(Recorder::new(options, &format!("{day}-part{part}")));
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Stops recording, completes the files of the part and returns its recorder. Called by the runner.
+pub fn end() -> Option<Recorder> {
+    ENABLED.store(false, Ordering::Relaxed);
+    let mut recorder = RECORDER.lock().unwrap().take()?;
+
+    if let Err(e) = recorder.finish() {
+        eprintln!("Failed to write visualisation: {e}");
+    }
+
+    Some(recorder)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{sample_steps, Color, Frame, Recorder, VizMode, VizOptions};
+    use std::fs;
+
+    fn frame() -> Frame {
+        Frame::from_grid(&[vec!['#', '.'], vec!['.']], |c| *c)
+    }
+
+    #[test]
+    fn pads_rows() {
+        assert_eq!(frame().to_ansi(), "#.\n. \n");
+    }
+
+    #[test]
+    fn highlights_cells() {
+        let frame = frame()
+            .highlight((1, 0), Color::Red)
+            .highlight((5, 5), Color::Blue);
+
+        assert_eq!(frame.to_ansi(), "#\x1b[1m\x1b[31m.\x1b[0m\n. \n");
+    }
+
+    #[test]
+    fn renders_ppm() {
+        let image = frame().highlight((0, 1), Color::Green).to_ppm(2);
+        let header = b"P6\n4 4\n255\n";
+
+        assert_eq!(&image[..header.len()], header);
+        assert_eq!(image.len(), header.len() + 4 * 4 * 3);
+        // the first pixel is a light cell, the first pixel of the third row the highlighted one.
+        assert_eq!(&image[header.len()..header.len() + 3], &[200, 200, 200]);
+        let third_row = header.len() + 2 * 4 * 3;
+        assert_eq!(&image[third_row..third_row + 3], &[133, 153, 0]);
+    }
+
+    #[test]
+    fn renders_png() {
+        let image = frame().highlight((0, 1), Color::Green).to_png(2).unwrap();
+
+        let decoder = png::Decoder::new(image.as_slice());
+        let mut reader = decoder.read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+
+        assert_eq!((info.width, info.height), (4, 4));
+        assert_eq!(&pixels[..3], &[200, 200, 200]);
+        assert_eq!(&pixels[2 * 4 * 3..2 * 4 * 3 + 3], &[133, 153, 0]);
+    }
+
+    #[test]
+    fn assembles_gif() {
+        let dir = std::env::temp_dir().join(format!("aoc-viz-gif-{}", std::process::id()));
+        let mut recorder = Recorder::new(
+            VizOptions {
+                mode: VizMode::Gif,
+                fps: 4,
+                dir: dir.clone(),
+                scale: 1,
+                ..VizOptions::default()
+            },
+            "01-part1",
+        );
+
+        recorder.record(&frame()).unwrap();
+        // larger frames are cropped to the size of the first one.
+        recorder
+            .record(&Frame::from_grid(&vec![vec!['#'; 3]; 3], |c| *c).highlight((1, 1), Color::Red))
+            .unwrap();
+        recorder.finish().unwrap();
+
+        let mut decoder = gif::DecodeOptions::new()
+            .read_info(fs::File::open(recorder.gif_path()).unwrap())
+            .unwrap();
+        assert_eq!((decoder.width(), decoder.height()), (2, 2));
+
+        let first = decoder.read_next_frame().unwrap().unwrap();
+        assert_eq!(first.delay, 25);
+        assert_eq!(&*first.buffer, &[1, 0, 0, 0]);
+
+        let second = decoder.read_next_frame().unwrap().unwrap();
+        assert_eq!(&*second.buffer, &[1, 1, 1, 2]);
+        assert!(decoder.read_next_frame().unwrap().is_none());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn samples_steps() {
+        assert_eq!(sample_steps(10, 4).collect::<Vec<_>>(), [2, 5, 7, 10]);
+        assert_eq!(sample_steps(3, 500).collect::<Vec<_>>(), [1, 2, 3]);
+        assert_eq!(sample_steps(10, 0).count(), 0);
+        assert_eq!(sample_steps(0, 500).count(), 0);
+    }
+
+    #[test]
+    fn caps_frames() {
+        let dir = std::env::temp_dir().join(format!("aoc-viz-{}", std::process::id()));
+        let mut recorder = Recorder::new(
+            VizOptions {
+                mode: VizMode::Ppm,
+                max_frames: 2,
+                dir: dir.clone(),
+                ..VizOptions::default()
+            },
+            "01-part1",
+        );
+
+        assert!(recorder.record(&frame()).unwrap());
+        assert!(recorder.record(&frame()).unwrap());
+        assert!(!recorder.record(&frame()).unwrap());
+        assert_eq!(recorder.frames(), 2);
+        assert!(recorder.frame_path(1).exists());
+        assert!(!recorder.frame_path(2).exists());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}